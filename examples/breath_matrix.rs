@@ -0,0 +1,79 @@
+use std::path::PathBuf;
+
+use clap::Parser;
+use itertools::Itertools as _;
+
+use wizardry_kod_util::*;
+
+/// 原作の ROM からデータを抽出し、どの装備がどのモンスターの息を軽減するかの表を出力する。
+#[derive(Debug, Parser)]
+struct Cli {
+    /// 原作の iNES ROM ファイル。
+    path_ines: PathBuf,
+}
+
+fn main() -> anyhow::Result<()> {
+    let cli = Cli::parse();
+
+    let rom = Rom::from_ines_file(cli.path_ines)?;
+    let items = extract::extract_items(&rom);
+    let monsters = extract::extract_monsters(&rom);
+
+    let matrix = breath_protection_matrix(&items, &monsters);
+
+    let monster_ids = (0..monsters.len())
+        .filter(|&id| !monsters[id].breath_elements.is_empty())
+        .collect_vec();
+
+    println!("## 息を吐くモンスター");
+    println!();
+    println!("| ID | 名前 | 属性 | ダメージ期待値 | 最大ダメージ | 軽減する装備 |");
+    println!("| --: | -- | -- | --: | --: | -- |");
+
+    for &id in &monster_ids {
+        let monster = &monsters[id];
+        let damage = breath_damage_distribution(monster, Elements::default());
+        let protectors = (0..items.len())
+            .filter(|&item_id| matrix[item_id][id])
+            .map(extract::item_true_name)
+            .join("<br>");
+
+        println!(
+            "| {id} | {} | {} | {:.1} | {} | {protectors} |",
+            extract::monster_true_name(id),
+            ElementsDisplayAbbrev::new(monster.breath_elements, " "),
+            damage.mean(),
+            damage.max_value(),
+        );
+    }
+
+    println!();
+    println!("## 装備ごとの軽減対象");
+    println!();
+    println!(
+        "| ID | 名前 | 抵抗 | {} |",
+        monster_ids
+            .iter()
+            .map(|&id| extract::monster_true_name(id))
+            .join(" | ")
+    );
+    println!("| --: | -- | -- |{}", " :-: |".repeat(monster_ids.len()));
+
+    for (item_id, row) in matrix.iter().enumerate() {
+        if !row.iter().any(|&protects| protects) {
+            continue;
+        }
+
+        let cells = monster_ids
+            .iter()
+            .map(|&id| if row[id] { "半" } else { "" })
+            .join(" | ");
+        println!(
+            "| {item_id} | {} | {} | {cells} |",
+            extract::item_true_name(item_id),
+            ElementsDisplayAbbrev::new(items[item_id].element_resistance, " "),
+        );
+    }
+
+    Ok(())
+}
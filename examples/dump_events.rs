@@ -0,0 +1,31 @@
+use std::path::PathBuf;
+
+use clap::Parser;
+
+use wizardry_kod_util::*;
+
+/// 原作の ROM から特殊マスのイベントを抽出し、疑似コードとして出力する。
+#[derive(Debug, Parser)]
+struct Cli {
+    /// 原作の iNES ROM ファイル。
+    path_ines: PathBuf,
+}
+
+fn main() -> anyhow::Result<()> {
+    let cli = Cli::parse();
+
+    let rom = Rom::from_ines_file(cli.path_ines)?;
+
+    let item_names: Vec<_> = (0..extract::ITEM_COUNT)
+        .map(extract::item_true_name)
+        .collect();
+    let monster_names: Vec<_> = (0..extract::MONSTER_COUNT)
+        .map(extract::monster_true_name)
+        .collect();
+
+    for event in extract::extract_events(&rom) {
+        println!("{}", EventDisplay::new(&event, &item_names, &monster_names));
+    }
+
+    Ok(())
+}
@@ -0,0 +1,41 @@
+use std::path::PathBuf;
+
+use anyhow::Context as _;
+use clap::Parser;
+
+use wizardry_kod_util::*;
+
+/// 原作の ROM から全テキストを抽出し、翻訳用の TSV 表として出力する。
+#[derive(Debug, Parser)]
+struct Cli {
+    /// 原作の iNES ROM ファイル。
+    path_ines: PathBuf,
+
+    /// 出力先ファイル (省略時は標準出力)。
+    path_out: Option<PathBuf>,
+
+    /// 追加で抽出するテキストのポインタテーブル (`分類,BB:AAAA,個数,テキストのバンク`、複数指定可)。
+    ///
+    /// 分類は dialogue, menu, shop, temple, inn など。
+    #[arg(long = "table")]
+    tables: Vec<TextTable>,
+}
+
+fn main() -> anyhow::Result<()> {
+    let cli = Cli::parse();
+
+    let rom = Rom::from_ines_file(cli.path_ines)?;
+
+    let entries = extract::extract_texts(&rom, &cli.tables);
+
+    match cli.path_out {
+        Some(path) => {
+            let file = std::fs::File::create(&path)
+                .with_context(|| format!("cannot create '{}'", path.display()))?;
+            write_text_table(std::io::BufWriter::new(file), &entries)?;
+        }
+        None => write_text_table(std::io::stdout().lock(), &entries)?,
+    }
+
+    Ok(())
+}
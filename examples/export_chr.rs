@@ -0,0 +1,98 @@
+use std::path::PathBuf;
+
+use anyhow::{ensure, Context as _};
+use clap::Parser;
+
+use wizardry_kod_util::*;
+
+/// 原作の ROM から CHR データを PNG 画像として書き出す。
+#[derive(Debug, Parser)]
+struct Cli {
+    /// 書き出す CHR バンクのID (0x400 バイト単位)。省略時は CHR-ROM 全体を書き出す。
+    #[arg(long)]
+    bank: Option<usize>,
+
+    /// 1 行あたりのタイル数。
+    #[arg(long, default_value_t = 16)]
+    columns: usize,
+
+    /// サブパレット (`0xRRGGBB` 形式の 4 色をカンマ区切りで指定)。省略時はグレースケール。
+    #[arg(long, value_parser = parse_sub_palette, conflicts_with = "palette_ram")]
+    colors: Option<SubPaletteArg>,
+
+    /// PPU のパレット RAM のダンプ (32 バイト) ファイルを指定し、そのサブパレットを適用する。
+    #[arg(long)]
+    palette_ram: Option<PathBuf>,
+
+    /// `--palette-ram` 指定時に使うサブパレット番号 (`0..=3` は BG 用、`4..=7` はスプライト用)。
+    #[arg(long, default_value_t = 0, value_parser = clap::value_parser!(u8).range(0..8))]
+    sub_palette: u8,
+
+    /// `--palette-ram` 指定時に使うマスターパレットの種類。
+    #[arg(long, default_value = "basic")]
+    master: MasterPaletteKind,
+
+    /// 原作の iNES ROM ファイル。
+    path_ines: PathBuf,
+
+    /// 出力先の PNG ファイル。
+    path_png: PathBuf,
+}
+
+#[derive(Clone, Copy, Debug)]
+struct SubPaletteArg([Rgb; 4]);
+
+fn parse_sub_palette(s: &str) -> anyhow::Result<SubPaletteArg> {
+    let colors = s
+        .split(',')
+        .map(|c| {
+            let c = c.trim().trim_start_matches("0x");
+            u32::from_str_radix(c, 16)
+                .map(Rgb::from_u32)
+                .with_context(|| format!("invalid color: '{c}'"))
+        })
+        .collect::<anyhow::Result<Vec<_>>>()?;
+
+    ensure!(colors.len() == 4, "sub palette must have 4 colors");
+
+    Ok(SubPaletteArg(colors.try_into().unwrap()))
+}
+
+fn main() -> anyhow::Result<()> {
+    let cli = Cli::parse();
+
+    let rom = Rom::from_ines_file(cli.path_ines)?;
+
+    ensure!(cli.columns > 0, "--columns must be positive");
+    if let Some(bank) = cli.bank {
+        ensure!(
+            bank < CHR_BANK_COUNT,
+            "--bank must be in 0..{CHR_BANK_COUNT}, got {bank}"
+        );
+    }
+
+    let tiles = match cli.bank {
+        Some(bank) => decode_tiles(rom.chr_bank(bank)),
+        None => decode_tiles(rom.chr()),
+    };
+
+    let palette = if let Some(path) = cli.palette_ram {
+        let buf =
+            std::fs::read(&path).with_context(|| format!("cannot read '{}'", path.display()))?;
+        let buf: &[u8; 32] = buf
+            .as_slice()
+            .try_into()
+            .with_context(|| format!("palette RAM dump must be 32 bytes, got {}", buf.len()))?;
+        let game_palette = GamePalette::from_bytes(buf);
+        let rgbs = game_palette.to_rgb(&cli.master.palette());
+        let base = 4 * usize::from(cli.sub_palette);
+        rgbs[base..][..4].try_into().unwrap()
+    } else {
+        cli.colors.map_or(CHR_GRAYSCALE, |arg| arg.0)
+    };
+
+    let image = render_tile_sheet(&tiles, cli.columns).to_rgb(&palette);
+    image.save_png(cli.path_png)?;
+
+    Ok(())
+}
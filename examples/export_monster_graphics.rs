@@ -0,0 +1,39 @@
+use std::path::PathBuf;
+
+use anyhow::Context as _;
+use clap::Parser;
+
+use wizardry_kod_util::*;
+
+/// 原作の ROM から全モンスターの戦闘グラフィックを PNG 画像として書き出す。
+#[derive(Debug, Parser)]
+struct Cli {
+    /// マスターパレットの種類。
+    #[arg(long, default_value = "basic")]
+    master: MasterPaletteKind,
+
+    /// 原作の iNES ROM ファイル。
+    path_ines: PathBuf,
+
+    /// 出力先ディレクトリ。
+    dir_out: PathBuf,
+}
+
+fn main() -> anyhow::Result<()> {
+    let cli = Cli::parse();
+
+    let master = cli.master.palette();
+
+    let rom = Rom::from_ines_file(cli.path_ines)?;
+
+    std::fs::create_dir_all(&cli.dir_out)
+        .with_context(|| format!("cannot create '{}'", cli.dir_out.display()))?;
+
+    for id in 0..extract::MONSTER_COUNT {
+        let graphic = extract::extract_monster_graphic(&rom, id);
+        let image = graphic.render(&master);
+        image.save_png(cli.dir_out.join(format!("monster_{id:02}.png")))?;
+    }
+
+    Ok(())
+}
@@ -0,0 +1,71 @@
+use std::path::PathBuf;
+
+use clap::Parser;
+
+use wizardry_kod_util::*;
+
+/// 原作の ROM から各フロアの遭遇テーブルを抽出し、モンスターの出現確率を出力する。
+#[derive(Debug, Parser)]
+struct Cli {
+    /// 原作の iNES ROM ファイル。
+    path_ines: PathBuf,
+}
+
+fn main() -> anyhow::Result<()> {
+    let cli = Cli::parse();
+
+    let rom = Rom::from_ines_file(cli.path_ines)?;
+
+    let tables = extract::extract_encounter_tables(&rom);
+
+    for (floor, table) in tables.iter().enumerate() {
+        output_floor(floor, table);
+    }
+
+    Ok(())
+}
+
+fn output_floor(floor: usize, table: &EncounterTable) {
+    println!("## B{}F", floor + 1);
+    println!();
+
+    println!("| ID | モンスター | 確率 |");
+    println!("| --: | -- | --: |");
+    for (id, p) in table.monster_probabilities() {
+        let true_name = extract::monster_true_name(usize::from(id));
+        println!("| {id} | {true_name} | {:.2} % |", 100.0 * p);
+    }
+    println!();
+
+    if !table.rooms.is_empty() {
+        let squares: Vec<String> = table
+            .rooms
+            .iter()
+            .map(|room| match room.guardian {
+                RoomGuardian::Fixed { monster_id } => format!(
+                    "({}, {}): {}",
+                    room.x,
+                    room.y,
+                    extract::monster_true_name(usize::from(monster_id))
+                ),
+                RoomGuardian::Wandering => format!("({}, {})", room.x, room.y),
+            })
+            .collect();
+        println!(
+            "玄室 (番人の指定が無ければ上記の出現確率に従う): {}",
+            squares.join(" ")
+        );
+        println!();
+    }
+
+    for enc in &table.fixed_encounters {
+        let true_name = extract::monster_true_name(usize::from(enc.monster_id));
+        let remaining = if enc.remaining < 0 {
+            "無制限".to_owned()
+        } else {
+            format!("残り {} 回", enc.remaining)
+        };
+        println!("固定戦闘 ({}, {}): {true_name} ({remaining})", enc.x, enc.y);
+    }
+    println!();
+}
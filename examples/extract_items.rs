@@ -170,7 +170,7 @@ fn note_healing(healing: i8) -> Option<String> {
 
 fn note_use(use_spell_id: u8, usable_in_camp: bool, usable_in_battle: bool) -> Option<String> {
     (usable_in_camp || usable_in_battle).then(|| {
-        let spell_name = extract::spell_name(usize::from(use_spell_id));
+        let spell_name = Spell::from_id(use_spell_id).map_or("(不明)", Spell::name);
         let camp = usable_in_camp.then_some("キャンプ");
         let battle = usable_in_battle.then_some("戦闘");
         format!(
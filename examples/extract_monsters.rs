@@ -11,6 +11,25 @@ use wizardry_kod_util::*;
 struct Cli {
     /// 原作の iNES ROM ファイル。
     path_ines: PathBuf,
+
+    /// 討伐数によるレベルアップの目安に使う経験値テーブル (`閾値,閾値,...+増分` 形式)。
+    #[arg(long, requires = "level")]
+    xp_table: Option<XpTable>,
+
+    /// 討伐数によるレベルアップの目安を示す現在レベル。
+    #[arg(long, requires = "xp_table")]
+    level: Option<u32>,
+
+    /// 宝箱に罠が仕掛けられている確率 (%)。指定すると各罠の確率を示す (種別は一様に選ばれると仮定)。
+    #[arg(long, value_parser = clap::value_parser!(u8).range(0..=100))]
+    trap_percent: Option<u8>,
+}
+
+/// 指定したレベルから次のレベルに上がるのに必要な経験値。
+#[derive(Clone, Copy, Debug)]
+struct LevelUp {
+    level: u32,
+    xp: u64,
 }
 
 fn main() -> anyhow::Result<()> {
@@ -19,17 +38,36 @@ fn main() -> anyhow::Result<()> {
     let rom = Rom::from_ines_file(cli.path_ines)?;
 
     let monsters = extract::extract_monsters(&rom);
+    let drop_tables = extract::extract_drop_tables(&rom)?;
+
+    let level_up = match (cli.xp_table, cli.level) {
+        (Some(table), Some(level)) => {
+            anyhow::ensure!(level >= 1, "level must be >= 1");
+            let xp = table
+                .xp_to_next_level(level)
+                .ok_or_else(|| anyhow::anyhow!("level {level} is the maximum"))?;
+            Some(LevelUp { level, xp })
+        }
+        _ => None,
+    };
 
-    output_markdown(monsters);
+    let trapped_probability = cli.trap_percent.map(|percent| f64::from(percent) / 100.0);
+
+    output_markdown(monsters, &drop_tables, level_up, trapped_probability);
 
     Ok(())
 }
 
-fn output_markdown(monsters: Vec<Monster>) {
+fn output_markdown(
+    monsters: Vec<Monster>,
+    drop_tables: &[DropTable],
+    level_up: Option<LevelUp>,
+    trapped_probability: Option<f64>,
+) {
     output_markdown_header();
 
     for (id, monster) in monsters.into_iter().enumerate() {
-        output_markdown_row(id, monster);
+        output_markdown_row(id, monster, drop_tables, level_up, trapped_probability);
     }
 }
 
@@ -57,7 +95,13 @@ fn output_markdown_header() {
     );
 }
 
-fn output_markdown_row(id: usize, monster: Monster) {
+fn output_markdown_row(
+    id: usize,
+    monster: Monster,
+    drop_tables: &[DropTable],
+    level_up: Option<LevelUp>,
+    trapped_probability: Option<f64>,
+) {
     let Monster {
         name_known_singular: _,
         name_known_plural,
@@ -97,9 +141,11 @@ fn output_markdown_row(id: usize, monster: Monster) {
     row.ac(format!("{ac}"));
     row.spell_resistance(format!("{spell_resistance}/256"));
     row.melee(melee_dice_exprs.iter().join("<br>"));
-    row.xp(format!("{xp}"));
+    row.xp(fmt_xp(xp, level_up));
     row.drop_(format!(
-        "徘徊: {drop_table_id_wandering}<br>玄室: {drop_table_id_guardian}"
+        "徘徊: {}<br>玄室: {}",
+        fmt_drop(drop_tables, drop_table_id_wandering, trapped_probability),
+        fmt_drop(drop_tables, drop_table_id_guardian, trapped_probability),
     ));
     row.spawn(format!("{spawn_dice_expr}"));
     row.follower(format!(
@@ -120,6 +166,70 @@ fn output_markdown_row(id: usize, monster: Monster) {
     row.build().unwrap().print();
 }
 
+fn fmt_xp(xp: u64, level_up: Option<LevelUp>) -> String {
+    let Some(LevelUp {
+        level,
+        xp: xp_needed,
+    }) = level_up
+    else {
+        return format!("{xp}");
+    };
+
+    if xp == 0 {
+        return format!("{xp}");
+    }
+
+    let kills = xp_needed.div_ceil(xp);
+    // `LevelUp` は次のレベルがある場合のみ作られる。
+    let next = level.saturating_add(1);
+    format!("{xp}<br>(Lv{level}→{next}: {kills} 体)")
+}
+
+fn fmt_drop(drop_tables: &[DropTable], id: u8, trapped_probability: Option<f64>) -> String {
+    let Some(table) = drop_tables.get(usize::from(id)) else {
+        return format!("{id} (不明)");
+    };
+
+    let golds = table
+        .entries
+        .iter()
+        .filter_map(|entry| match entry.content {
+            DropContent::Gold(gold) => Some(format!("{gold} G ({} %)", entry.percent)),
+            DropContent::Item(_) => None,
+        });
+
+    let items = table.item_probabilities().into_iter().map(|(item_id, p)| {
+        let item_id = usize::from(item_id);
+        let true_name = if item_id < extract::ITEM_COUNT {
+            extract::item_true_name(item_id).to_owned()
+        } else {
+            format!("#{item_id}")
+        };
+        format!("{true_name} ({:.1} %)", 100.0 * p)
+    });
+
+    let mut ss = vec![format!("{id}")];
+    if table.chest {
+        let traps = match trapped_probability {
+            Some(p) => table
+                .trap_probabilities(p)
+                .into_iter()
+                .map(|(kind, p)| format!("{} {:.1} %", kind.name(), 100.0 * p))
+                .join(", "),
+            None => TrapKindsDisplay::new(table.trap_kinds, ", ").to_string(),
+        };
+        if traps.is_empty() {
+            ss.push("宝箱".to_owned());
+        } else {
+            ss.push(format!("宝箱 (罠: {traps})"));
+        }
+    }
+    ss.extend(golds);
+    ss.extend(items);
+
+    ss.into_iter().join(" / ")
+}
+
 fn note_spells(mage: u8, cleric: u8) -> Option<String> {
     let mut ss = Vec::<String>::new();
 
@@ -0,0 +1,47 @@
+use std::path::PathBuf;
+
+use clap::Parser;
+use itertools::Itertools as _;
+
+use wizardry_kod_util::*;
+
+/// 原作の ROM から呪文データを抽出する。
+#[derive(Debug, Parser)]
+struct Cli {
+    /// 原作の iNES ROM ファイル。
+    path_ines: PathBuf,
+}
+
+fn main() -> anyhow::Result<()> {
+    let cli = Cli::parse();
+
+    let rom = Rom::from_ines_file(cli.path_ines)?;
+
+    println!("| ID | 名前 | 系統 | Lv | 対象 | 効果量 | 属性 | 使用 |");
+    println!("| --: | -- | -- | --: | -- | -- | -- | -- |");
+
+    for (spell, data) in Spell::iter().zip(extract::extract_spell_datas(&rom)?) {
+        let dice = data
+            .dice_expr
+            .map_or_else(String::new, |dice| dice.to_string());
+        let usable = [
+            data.usable_in_camp.then_some("キャンプ"),
+            data.usable_in_battle.then_some("戦闘"),
+        ]
+        .into_iter()
+        .flatten()
+        .join("/");
+
+        println!(
+            "| {} | {} | {} | {} | {} | {dice} | {} | {usable} |",
+            spell.to_id(),
+            spell.name(),
+            spell.school().name(),
+            spell.level(),
+            data.target.name(),
+            ElementsDisplayAbbrev::new(data.elements, " "),
+        );
+    }
+
+    Ok(())
+}
@@ -0,0 +1,70 @@
+use std::path::PathBuf;
+
+use anyhow::{ensure, Context as _};
+use clap::Parser;
+
+use wizardry_kod_util::*;
+
+/// 原作の ROM から迷宮データを抽出し、2 地点間の最短経路を求める。
+#[derive(Debug, Parser)]
+struct Cli {
+    /// 隠し扉を通らない。
+    #[arg(long)]
+    no_secret_doors: bool,
+
+    /// MALOR を使う場合、そのコスト (歩数換算)。
+    #[arg(long)]
+    malor_cost: Option<u32>,
+
+    /// 原作の iNES ROM ファイル。
+    path_ines: PathBuf,
+
+    /// 出発地点 (`フロア,x,y`、フロアは 1 始まり)。
+    #[arg(value_parser = parse_position)]
+    start: Position,
+
+    /// 目的地 (`フロア,x,y`、フロアは 1 始まり)。
+    #[arg(value_parser = parse_position)]
+    goal: Position,
+}
+
+fn parse_position(s: &str) -> anyhow::Result<Position> {
+    let fields = s
+        .split(',')
+        .map(|f| f.trim().parse::<usize>().context("invalid number"))
+        .collect::<anyhow::Result<Vec<_>>>()?;
+    ensure!(fields.len() == 3, "position must be 'floor,x,y'");
+
+    let [floor, x, y] = fields.try_into().unwrap();
+    ensure!(
+        (1..=extract::FLOOR_COUNT).contains(&floor),
+        "floor must be in 1..={}",
+        extract::FLOOR_COUNT
+    );
+    ensure!(x < FLOOR_WIDTH && y < FLOOR_HEIGHT, "position out of range");
+
+    Ok(Position::new(floor - 1, x, y))
+}
+
+fn main() -> anyhow::Result<()> {
+    let cli = Cli::parse();
+
+    let rom = Rom::from_ines_file(cli.path_ines)?;
+    let floors = extract::extract_floors(&rom);
+
+    let opts = RouteOptions {
+        use_secret_doors: !cli.no_secret_doors,
+        malor_cost: cli.malor_cost,
+    };
+
+    let Some(route) = find_route(&floors, cli.start, cli.goal, &opts) else {
+        println!("到達不能");
+        return Ok(());
+    };
+
+    for (i, step) in route.iter().enumerate() {
+        println!("{}. {step}", i + 1);
+    }
+
+    Ok(())
+}
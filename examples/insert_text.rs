@@ -0,0 +1,66 @@
+use std::path::PathBuf;
+
+use anyhow::{ensure, Context as _};
+use clap::Parser;
+
+use wizardry_kod_util::*;
+
+/// 編集済みのテキスト表 (`dump_text` の出力) を ROM に書き戻す。
+#[derive(Debug, Parser)]
+struct Cli {
+    /// 原作の iNES ROM ファイル。
+    path_ines: PathBuf,
+
+    /// 編集済みのテキスト表 (TSV)。
+    path_table: PathBuf,
+
+    /// 出力先の iNES ROM ファイル。
+    path_out: PathBuf,
+
+    /// 伸びたテキストの移動先として使ってよい領域 (`BB:AAAA+LEN`、複数指定可)。
+    ///
+    /// 指定しなければテキストは移動されず、元の範囲に収まらないものは失敗となる。
+    #[arg(long = "free")]
+    free_regions: Vec<FreeRegion>,
+
+    /// テキストのポインタテーブル (`dump_text` の `--table` と同じものを指定する)。
+    #[arg(long = "table")]
+    tables: Vec<TextTable>,
+}
+
+fn main() -> anyhow::Result<()> {
+    let cli = Cli::parse();
+
+    let mut rom = Rom::from_ines_file(cli.path_ines)?;
+
+    let entries = {
+        let file = std::fs::File::open(&cli.path_table)
+            .with_context(|| format!("cannot open '{}'", cli.path_table.display()))?;
+        read_text_table(std::io::BufReader::new(file))?
+    };
+
+    let report = insert_texts(&mut rom, &entries, &cli.tables, &cli.free_regions);
+
+    println!("updated: {}", report.updated_count);
+    for (from, to) in &report.relocated {
+        println!("relocated: {from} -> {to}");
+    }
+    for (bank, usage) in &report.banks {
+        println!(
+            "bank {bank:02}: free {} -> {} bytes, overflow {} bytes",
+            usage.free_before, usage.free_after, usage.overflow
+        );
+    }
+    for (location, reason) in &report.failures {
+        eprintln!("failed: {location}: {reason}");
+    }
+    ensure!(
+        report.failures.is_empty(),
+        "{} texts could not be inserted; ROM not saved",
+        report.failures.len()
+    );
+
+    rom.save_ines_file(cli.path_out)?;
+
+    Ok(())
+}
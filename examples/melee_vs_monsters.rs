@@ -0,0 +1,92 @@
+use std::path::PathBuf;
+
+use anyhow::Context as _;
+use clap::Parser;
+
+use wizardry_kod_util::*;
+
+/// 原作の ROM からデータを抽出し、キャラクターの打撃で各モンスターを 1 ターンで倒す確率を求める。
+#[derive(Debug, Parser)]
+struct Cli {
+    /// 職業ID (`0..=7`)。
+    #[arg(long, default_value_t = 0)]
+    class: u8,
+
+    /// レベル。
+    #[arg(long, default_value_t = 10)]
+    level: u32,
+
+    /// 力。
+    #[arg(long, default_value_t = 15)]
+    strength: u8,
+
+    /// 装備するアイテムのID (カンマ区切り)。
+    #[arg(long, value_delimiter = ',')]
+    equip: Vec<u8>,
+
+    /// 原作の iNES ROM ファイル。
+    path_ines: PathBuf,
+}
+
+fn main() -> anyhow::Result<()> {
+    let cli = Cli::parse();
+
+    let class = Class::from_id(cli.class).context("invalid class ID")?;
+
+    let rom = Rom::from_ines_file(cli.path_ines)?;
+    let items = extract::extract_items(&rom);
+    let monsters = extract::extract_monsters(&rom);
+
+    let mut stats = Stats::default();
+    stats.set(Stat::Strength, cli.strength);
+
+    let mut chara = Character {
+        race: Race::Human,
+        class,
+        alignment: Alignment::Neutral,
+        stats,
+        level: cli.level,
+        hp: 1,
+        max_hp: 1,
+        age: 20,
+        status: CharacterStatus::Ok,
+        poisoned: false,
+        equipment: vec![],
+    };
+    for &id in &cli.equip {
+        let item = items
+            .get(usize::from(id))
+            .with_context(|| format!("invalid item ID: {id}"))?;
+        chara
+            .equip(id, item.clone())
+            .with_context(|| format!("cannot equip item {id}"))?;
+    }
+
+    let attack = MeleeAttack::from_character(&chara);
+
+    println!(
+        "| ID | 名前 | AC | 命中率 | 打撃回数 | ダメージ期待値 | 首はね | 1 ターンで倒す確率 |"
+    );
+    println!("| --: | -- | --: | --: | --: | --: | --: | --: |");
+
+    for (id, monster) in monsters.iter().enumerate() {
+        let outcome = melee_against_monster(&attack, monster);
+
+        let hp = monster.hp_dice_expr;
+        let hp = dice_distribution(hp.count(), hp.face(), i32::from(hp.bias_decoded()), 1);
+
+        println!(
+            "| {id} | {} | {} | {:.1} % | {} | {:.1}{} | {:.1} % | {:.1} % |",
+            extract::monster_true_name(id),
+            monster.ac,
+            100.0 * outcome.hit_probability,
+            outcome.swing_count,
+            outcome.damage.mean(),
+            if outcome.slay { " (倍打)" } else { "" },
+            100.0 * outcome.critical_probability,
+            100.0 * outcome.kill_probability_dist(&hp),
+        );
+    }
+
+    Ok(())
+}
@@ -0,0 +1,112 @@
+use std::path::PathBuf;
+
+use anyhow::Context as _;
+use clap::Parser;
+use itertools::Itertools as _;
+
+use wizardry_kod_util::*;
+
+/// 原作の ROM からモンスターデータを抽出し、各モンスターの打撃が前衛に与える被害を求める。
+///
+/// 命中判定と追加効果を免れる確率は原作のルーチンが未解析なので、仮定に基づく値である。
+#[derive(Debug, Parser)]
+struct Cli {
+    /// 前衛の AC。
+    #[arg(long, default_value_t = 0, allow_hyphen_values = true)]
+    ac: i32,
+
+    /// 毒を免れる確率 (%)。原作の判定式は未解析なので仮定した値を与える。
+    #[arg(long, default_value_t = 0.0, value_parser = parse_percent)]
+    save_poison: f64,
+
+    /// 麻痺を免れる確率 (%)。
+    #[arg(long, default_value_t = 0.0, value_parser = parse_percent)]
+    save_paralyze: f64,
+
+    /// 石化を免れる確率 (%)。
+    #[arg(long, default_value_t = 0.0, value_parser = parse_percent)]
+    save_petrify: f64,
+
+    /// レベルドレインを免れる確率 (%)。
+    #[arg(long, default_value_t = 0.0, value_parser = parse_percent)]
+    save_drain: f64,
+
+    /// 首はねを免れる確率 (%)。
+    #[arg(long, default_value_t = 0.0, value_parser = parse_percent)]
+    save_critical: f64,
+
+    /// 前衛が抵抗する属性 (名称または略称、カンマ区切り)。
+    #[arg(long, value_delimiter = ',', value_parser = parse_element)]
+    resist: Vec<Element>,
+
+    /// 原作の iNES ROM ファイル。
+    path_ines: PathBuf,
+}
+
+fn parse_percent(s: &str) -> anyhow::Result<f64> {
+    let percent: f64 = s.parse().with_context(|| format!("invalid percent: {s}"))?;
+    anyhow::ensure!(
+        (0.0..=100.0).contains(&percent),
+        "percent must be in 0..=100, got {percent}"
+    );
+
+    Ok(percent)
+}
+
+fn parse_element(s: &str) -> anyhow::Result<Element> {
+    Element::iter()
+        .find(|elem| elem.name() == s || elem.name_abbrev() == s)
+        .with_context(|| format!("unknown element: {s}"))
+}
+
+fn main() -> anyhow::Result<()> {
+    let cli = Cli::parse();
+
+    let rom = Rom::from_ines_file(cli.path_ines)?;
+    let monsters = extract::extract_monsters(&rom);
+
+    let defender = MeleeDefender {
+        ac: cli.ac,
+        element_resistance: cli
+            .resist
+            .into_iter()
+            .fold(Elements::default(), |acc, elem| acc | elem),
+        saves: OnHitSaves {
+            poison: cli.save_poison / 100.0,
+            paralyze: cli.save_paralyze / 100.0,
+            petrify: cli.save_petrify / 100.0,
+            drain: cli.save_drain / 100.0,
+            critical: cli.save_critical / 100.0,
+        },
+    };
+
+    println!("| ID | 名前 | 打撃 | 命中率 | ダメージ期待値 | 最大ダメージ | 追加効果 |");
+    println!("| --: | -- | -- | --: | --: | --: | -- |");
+
+    for (id, monster) in monsters.iter().enumerate() {
+        if monster.melee_dice_exprs.is_empty() {
+            continue;
+        }
+
+        let outcome = monster_attack_against(monster, &defender);
+        let effects = outcome
+            .effects
+            .iter()
+            .map(|(effect, p)| match effect {
+                OnHitEffect::Drain(xl) => format!("{}{xl} {:.1} %", effect.name(), 100.0 * p),
+                _ => format!("{} {:.1} %", effect.name(), 100.0 * p),
+            })
+            .join("<br>");
+
+        println!(
+            "| {id} | {} | {} | {:.1} % | {:.1} | {} | {effects} |",
+            extract::monster_true_name(id),
+            monster.melee_dice_exprs.iter().join(" "),
+            100.0 * monster_hit_probability(monster, defender.ac),
+            outcome.damage.mean(),
+            outcome.damage.max_value(),
+        );
+    }
+
+    Ok(())
+}
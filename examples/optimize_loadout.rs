@@ -0,0 +1,142 @@
+use std::path::PathBuf;
+
+use anyhow::Context as _;
+use clap::{Parser, ValueEnum};
+use itertools::Itertools as _;
+
+use wizardry_kod_util::*;
+
+/// 原作の ROM からアイテムデータを抽出し、装備の組み合わせのパレート最適解を求める。
+#[derive(Debug, Parser)]
+struct Cli {
+    /// 職業ID (`0..=7`)。
+    #[arg(long, default_value_t = 0)]
+    class: u8,
+
+    /// 性格ID (`0..=2`)。
+    #[arg(long, default_value_t = 0)]
+    alignment: u8,
+
+    /// レベル。
+    #[arg(long, default_value_t = 10)]
+    level: u32,
+
+    /// 能力値 (力, 知恵, 信仰心, 生命力, 素早さ, 運 の順にカンマ区切り)。
+    #[arg(long, value_delimiter = ',', default_value = "15,10,10,10,10,10")]
+    stats: Vec<u8>,
+
+    /// 所持しているアイテムのID (カンマ区切り。省略時は全アイテム)。
+    #[arg(long, value_delimiter = ',')]
+    owned: Option<Vec<u8>>,
+
+    /// 打撃の対象とするモンスター種別 (名称または略称、カンマ区切り)。
+    #[arg(long, value_delimiter = ',', value_parser = parse_monster_kind)]
+    target_kinds: Vec<MonsterKind>,
+
+    /// 打撃の対象の AC (命中率の計算に使う)。
+    #[arg(long, default_value_t = 10, allow_hyphen_values = true)]
+    target_ac: i32,
+
+    /// 抵抗したい属性 (名称または略称、カンマ区切り)。
+    #[arg(long, value_delimiter = ',', value_parser = parse_element)]
+    elements: Vec<Element>,
+
+    /// 最適化の目的 (カンマ区切り)。
+    #[arg(long, value_delimiter = ',', default_value = "ac,damage")]
+    objectives: Vec<Objective>,
+
+    /// 原作の iNES ROM ファイル。
+    path_ines: PathBuf,
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq, ValueEnum)]
+enum Objective {
+    Ac,
+    Damage,
+    Resistance,
+}
+
+impl From<Objective> for LoadoutObjective {
+    fn from(objective: Objective) -> Self {
+        match objective {
+            Objective::Ac => Self::MinAc,
+            Objective::Damage => Self::MaxMeleeDamage,
+            Objective::Resistance => Self::MaxResistance,
+        }
+    }
+}
+
+fn parse_monster_kind(s: &str) -> anyhow::Result<MonsterKind> {
+    MonsterKind::iter()
+        .find(|kind| kind.name() == s || kind.name_abbrev() == s)
+        .with_context(|| format!("unknown monster kind: {s}"))
+}
+
+fn parse_element(s: &str) -> anyhow::Result<Element> {
+    Element::iter()
+        .find(|elem| elem.name() == s || elem.name_abbrev() == s)
+        .with_context(|| format!("unknown element: {s}"))
+}
+
+fn main() -> anyhow::Result<()> {
+    let cli = Cli::parse();
+
+    let class = Class::from_id(cli.class).context("invalid class ID")?;
+    let alignment = Alignment::from_id(cli.alignment).context("invalid alignment ID")?;
+
+    let stats = Stats::new(
+        cli.stats
+            .try_into()
+            .map_err(|_| anyhow::anyhow!("--stats must have 6 values"))?,
+    );
+
+    let rom = Rom::from_ines_file(cli.path_ines)?;
+    let items = extract::extract_items(&rom);
+
+    let query = LoadoutQuery {
+        character: Character {
+            race: Race::Human,
+            class,
+            alignment,
+            stats,
+            level: cli.level,
+            hp: 1,
+            max_hp: 1,
+            age: 20,
+            status: CharacterStatus::Ok,
+            poisoned: false,
+            equipment: vec![],
+        },
+        owned_item_ids: cli.owned,
+        target_monster_kinds: cli
+            .target_kinds
+            .into_iter()
+            .fold(MonsterKinds::default(), |acc, kind| acc | kind),
+        target_ac: cli.target_ac,
+        elements: cli
+            .elements
+            .into_iter()
+            .fold(Elements::default(), |acc, elem| acc | elem),
+        objectives: cli.objectives.into_iter().map(Into::into).collect(),
+    };
+
+    println!("| AC | 打撃ダメージ期待値 | クリティカル率 | 抵抗 | 装備 |");
+    println!("| --: | --: | --: | -- | -- |");
+
+    for loadout in optimize_loadout(&items, &query)? {
+        let names = loadout
+            .item_ids
+            .iter()
+            .map(|&id| extract::item_true_name(usize::from(id)))
+            .join(" / ");
+        println!(
+            "| {} | {:.1} | {:.1}% | {} | {names} |",
+            loadout.ac,
+            loadout.expected_melee_damage,
+            100.0 * loadout.critical_probability,
+            ElementsDisplayAbbrev::new(loadout.resisted_elements, " "),
+        );
+    }
+
+    Ok(())
+}
@@ -0,0 +1,93 @@
+use std::path::PathBuf;
+
+use anyhow::{bail, ensure, Context as _};
+use clap::{Parser, ValueEnum};
+
+use wizardry_kod_util::*;
+
+/// 原作の ROM から迷宮データを抽出し、地図を描画する。
+#[derive(Debug, Parser)]
+struct Cli {
+    /// 出力形式。
+    #[arg(long, value_enum, default_value_t = Format::Ascii)]
+    format: Format,
+
+    /// 隠し扉を表示する。
+    #[arg(long)]
+    show_secret_doors: bool,
+
+    /// パーティの位置と向き (例: `0,0,N`)。
+    #[arg(long, value_parser = parse_party)]
+    party: Option<(usize, usize, Direction)>,
+
+    /// 原作の iNES ROM ファイル。
+    path_ines: PathBuf,
+
+    /// フロア (`1..=6`)。
+    floor: usize,
+
+    /// 出力先ファイル (省略時は標準出力。PNG の場合は必須)。
+    path_out: Option<PathBuf>,
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq, ValueEnum)]
+enum Format {
+    Ascii,
+    Svg,
+    Png,
+}
+
+fn parse_party(s: &str) -> anyhow::Result<(usize, usize, Direction)> {
+    let fields: Vec<&str> = s.split(',').collect();
+    ensure!(fields.len() == 3, "party must be 'x,y,dir'");
+
+    let x: usize = fields[0].parse().context("invalid x")?;
+    let y: usize = fields[1].parse().context("invalid y")?;
+    let dir = match fields[2] {
+        "N" => Direction::North,
+        "E" => Direction::East,
+        "S" => Direction::South,
+        "W" => Direction::West,
+        dir => bail!("invalid direction: '{dir}'"),
+    };
+    ensure!(x < FLOOR_WIDTH && y < FLOOR_HEIGHT, "position out of range");
+
+    Ok((x, y, dir))
+}
+
+fn main() -> anyhow::Result<()> {
+    let cli = Cli::parse();
+
+    ensure!(
+        (1..=extract::FLOOR_COUNT).contains(&cli.floor),
+        "floor must be in 1..={}",
+        extract::FLOOR_COUNT
+    );
+
+    let rom = Rom::from_ines_file(cli.path_ines)?;
+    let floor = extract::extract_floor(&rom, cli.floor - 1);
+
+    let opts = MazeRenderOptions {
+        show_secret_doors: cli.show_secret_doors,
+        party: cli.party,
+    };
+
+    let text = match cli.format {
+        Format::Ascii => render_maze_ascii(&floor, &opts),
+        Format::Svg => render_maze_svg(&floor, &opts),
+        Format::Png => {
+            let path = cli.path_out.context("output path is required for PNG")?;
+            let font = extract::extract_font(&rom);
+            render_maze_image(&floor, &opts, Some(&font)).save_png(path)?;
+            return Ok(());
+        }
+    };
+
+    match cli.path_out {
+        Some(path) => std::fs::write(&path, text)
+            .with_context(|| format!("cannot write '{}'", path.display()))?,
+        None => print!("{text}"),
+    }
+
+    Ok(())
+}
@@ -0,0 +1,59 @@
+use std::path::PathBuf;
+
+use anyhow::{bail, Context as _};
+use clap::Parser;
+
+use wizardry_kod_util::*;
+
+/// 原作のフォントで文字列を描画し、PNG 画像として書き出す。
+///
+/// 文字列中の "之", "薬", "巻" はそれぞれ原作の対応する文字として扱われる。
+#[derive(Debug, Parser)]
+struct Cli {
+    /// 枠の幅 (文字数)。指定した場合、文字列がこれに収まらなければエラーになる。
+    #[arg(long)]
+    width: Option<usize>,
+
+    /// 原作の iNES ROM ファイル。
+    path_ines: PathBuf,
+
+    /// 出力先の PNG ファイル。
+    path_png: PathBuf,
+
+    /// 描画する文字列 (複数指定すると複数行になる)。
+    #[arg(required = true)]
+    texts: Vec<String>,
+}
+
+fn main() -> anyhow::Result<()> {
+    let cli = Cli::parse();
+
+    let rom = Rom::from_ines_file(cli.path_ines)?;
+    let font = extract::extract_font(&rom);
+
+    let lines = cli
+        .texts
+        .iter()
+        .map(|text| GameString::from_text(text).with_context(|| format!("cannot encode '{text}'")))
+        .collect::<anyhow::Result<Vec<_>>>()?;
+
+    if let Some(width) = cli.width {
+        for line in &lines {
+            if line.len() > width {
+                bail!(
+                    "'{line}' does not fit in {width} chars ({} chars)",
+                    line.len()
+                );
+            }
+        }
+    }
+
+    let image = match cli.width {
+        Some(width) if lines.len() == 1 => font.render_padded(&lines[0], width).unwrap(),
+        _ => font.render_lines(&lines),
+    };
+
+    image.to_rgb(&CHR_GRAYSCALE).save_png(cli.path_png)?;
+
+    Ok(())
+}
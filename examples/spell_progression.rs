@@ -0,0 +1,62 @@
+use anyhow::ensure;
+use clap::Parser;
+use itertools::Itertools as _;
+
+use wizardry_kod_util::*;
+
+/// 呪文習得の進み方を指定し、レベルごとの MP を出力する。
+#[derive(Debug, Parser)]
+struct Cli {
+    /// 魔術師系呪文の進み方 (`offset,interval` 形式)。
+    #[arg(long)]
+    mage: Option<SpellProgression>,
+
+    /// 僧侶系呪文の進み方 (`offset,interval` 形式)。
+    #[arg(long)]
+    cleric: Option<SpellProgression>,
+
+    /// 出力する最大のキャラクターレベル。
+    #[arg(long, default_value_t = 20)]
+    max_level: u32,
+}
+
+fn main() -> anyhow::Result<()> {
+    let cli = Cli::parse();
+
+    let prog = ClassSpellProgression {
+        mage: cli.mage,
+        cleric: cli.cleric,
+    };
+    ensure!(
+        prog.mage.is_some() || prog.cleric.is_some(),
+        "specify --mage and/or --cleric"
+    );
+
+    println!("| Lv | 魔術師 MP | 僧侶 MP | 新たに習得可能な呪文 |");
+    println!("| --: | -- | -- | -- |");
+
+    for level in 1..=cli.max_level {
+        let fmt_mp = |school| {
+            let mp = prog.mp(school, level);
+            if mp[0] == 0 {
+                String::new()
+            } else {
+                mp.iter().join("/")
+            }
+        };
+        let prev = prog.learnable_spells(level - 1).collect_vec();
+        let new_spells = prog
+            .learnable_spells(level)
+            .filter(|spell| !prev.contains(spell))
+            .map(|spell| spell.name())
+            .join(" ");
+
+        println!(
+            "| {level} | {} | {} | {new_spells} |",
+            fmt_mp(SpellSchool::Mage),
+            fmt_mp(SpellSchool::Cleric),
+        );
+    }
+
+    Ok(())
+}
@@ -0,0 +1,99 @@
+use std::path::PathBuf;
+
+use anyhow::Context as _;
+use clap::Parser;
+use itertools::Itertools as _;
+
+use wizardry_kod_util::*;
+
+/// 原作の ROM からデータを抽出し、攻撃呪文で各モンスターのグループを倒す数の分布を出力する。
+#[derive(Debug, Parser)]
+struct Cli {
+    /// グループ内のモンスターの数 (省略時は出現数ダイス式に従う)。
+    #[arg(long)]
+    group_size: Option<usize>,
+
+    /// 睡眠弱点を持たないモンスターが KATINO で眠る確率 (%)。原作の判定式は未解析なので仮定した値を与える。
+    #[arg(long, value_parser = parse_percent)]
+    sleep_percent: f64,
+
+    /// 睡眠弱点を持つモンスターが KATINO で眠る確率 (%)。
+    #[arg(long, value_parser = parse_percent)]
+    sleep_percent_sleepy: f64,
+
+    /// 睡眠弱点を持たないモンスターが即死呪文 (MAKANITO, LAKANITO, BADI) で死ぬ確率 (%)。
+    /// 原作の判定式は未解析なので仮定した値を与える。
+    #[arg(long, value_parser = parse_percent)]
+    death_percent: f64,
+
+    /// 睡眠弱点を持つモンスターが即死呪文で死ぬ確率 (%)。
+    #[arg(long, value_parser = parse_percent)]
+    death_percent_sleepy: f64,
+
+    /// 原作の iNES ROM ファイル。
+    path_ines: PathBuf,
+}
+
+fn parse_percent(s: &str) -> anyhow::Result<f64> {
+    let percent: f64 = s.parse().with_context(|| format!("invalid percent: {s}"))?;
+    anyhow::ensure!(
+        (0.0..=100.0).contains(&percent),
+        "percent must be in 0..=100, got {percent}"
+    );
+
+    Ok(percent)
+}
+
+fn main() -> anyhow::Result<()> {
+    let cli = Cli::parse();
+
+    let assumptions = SpellAssumptions {
+        sleep_probability: cli.sleep_percent / 100.0,
+        sleep_probability_sleepy: cli.sleep_percent_sleepy / 100.0,
+        death_probability: cli.death_percent / 100.0,
+        death_probability_sleepy: cli.death_percent_sleepy / 100.0,
+    };
+
+    let rom = Rom::from_ines_file(cli.path_ines)?;
+    let monsters = extract::extract_monsters(&rom);
+    let spells = Spell::iter()
+        .zip(extract::extract_spell_datas(&rom)?)
+        .filter(|(spell, data)| spell_effect(*spell, data).is_some())
+        .collect_vec();
+
+    for (id, monster) in monsters.iter().enumerate() {
+        let group_size = cli.group_size.map_or_else(
+            || monster_group_size_distribution(monster),
+            ValueDistribution::constant,
+        );
+
+        println!("## {id}: {}", extract::monster_true_name(id));
+        println!();
+        println!("| 呪文 | 有効率 | 1 体あたり | 倒す数の分布 |");
+        println!("| -- | --: | --: | -- |");
+
+        for (spell, data) in &spells {
+            let outcome =
+                cast_spell_against_group(*spell, data, monster, &group_size, &assumptions).unwrap();
+            let dist = outcome
+                .kill_count
+                .probs()
+                .iter()
+                .enumerate()
+                .filter(|&(_, &p)| p >= 0.0005)
+                .map(|(count, p)| format!("{count}: {:.1} %", 100.0 * p))
+                .join("<br>");
+
+            println!(
+                "| {} | {:.1} % | {:.1} % | {dist} |",
+                spell.name(),
+                100.0 * outcome.affect_probability,
+                100.0 * outcome.kill_probability,
+            );
+        }
+
+        println!();
+    }
+
+    Ok(())
+}
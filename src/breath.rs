@@ -0,0 +1,69 @@
+use crate::distribution::{dice_distribution, ValueDistribution};
+use crate::element::{Elements, Resistance};
+use crate::item::Item;
+use crate::monster::Monster;
+
+/// 現在の HP が `current_hp` のモンスターの息がキャラクター 1 人に与えるダメージを返す。
+///
+/// 息のダメージはモンスターの現在の HP の半分で、キャラクターが息の属性に抵抗していればさらに半減する。
+pub fn breath_damage(current_hp: u32, breath_elements: Elements, resistance: Elements) -> u32 {
+    Resistance::for_character(breath_elements, resistance).apply(current_hp / 2)
+}
+
+/// 無傷のモンスター (HP はダイス式に従う) の息がキャラクター 1 人に与えるダメージの分布を返す。
+///
+/// 息を吐かないモンスターなら常に 0。
+pub fn breath_damage_distribution(monster: &Monster, resistance: Elements) -> ValueDistribution {
+    if monster.breath_elements.is_empty() {
+        return ValueDistribution::constant(0);
+    }
+
+    let hp = monster.hp_dice_expr;
+    let hp = dice_distribution(hp.count(), hp.face(), i32::from(hp.bias_decoded()), 1);
+
+    hp.map(|hp| {
+        let hp = u32::try_from(hp).unwrap();
+        usize::try_from(breath_damage(hp, monster.breath_elements, resistance)).unwrap()
+    })
+}
+
+/// アイテムを装備していれば、モンスターの息のダメージが軽減されるかどうかを返す。
+pub fn item_protects_against_breath(item: &Item, monster: &Monster) -> bool {
+    !monster.breath_elements.is_empty()
+        && Resistance::for_character(monster.breath_elements, item.element_resistance)
+            != Resistance::None
+}
+
+/// 各アイテムが各モンスターの息を軽減するかどうかの表を返す。
+///
+/// 戻り値の `[item_id][monster_id]` 要素が、そのアイテムがそのモンスターの息を軽減するかどうか。
+pub fn breath_protection_matrix(items: &[Item], monsters: &[Monster]) -> Vec<Vec<bool>> {
+    items
+        .iter()
+        .map(|item| {
+            monsters
+                .iter()
+                .map(|monster| item_protects_against_breath(item, monster))
+                .collect()
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::element::Element;
+
+    use super::*;
+
+    #[test]
+    fn test_breath_damage() {
+        let fire = Elements::from(Element::Fire);
+        let cold = Elements::from(Element::Cold);
+
+        assert_eq!(breath_damage(51, fire, Elements::default()), 25);
+        assert_eq!(breath_damage(51, fire, cold), 25);
+        assert_eq!(breath_damage(51, fire, fire | cold), 12);
+
+        assert_eq!(Resistance::for_monster(fire, fire).apply(100), 0);
+    }
+}
@@ -0,0 +1,376 @@
+use crate::alignment::Alignment;
+use crate::class::Class;
+use crate::element::Elements;
+use crate::item::{Item, ItemKind, ItemMeleeDiceExpr};
+use crate::race::Race;
+use crate::stat::{Stat, Stats};
+
+/// 何も装備していないときの AC。
+pub const BASE_AC: i32 = 10;
+
+/// 1 ターンあたりの打撃回数の上限。
+pub const SWING_COUNT_MAX: u32 = 10;
+
+/// 素手の打撃ダメージダイス式。
+pub const UNARMED_DICE_EXPR: ItemMeleeDiceExpr = ItemMeleeDiceExpr::new(1, 2, 0);
+
+/// キャラクター。
+///
+/// 派生値 (AC, 命中補正など) は装備中のアイテムのデータから計算する。
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Character {
+    pub race: Race,
+    pub class: Class,
+    pub alignment: Alignment,
+    pub stats: Stats,
+    pub level: u32,
+    pub hp: u16,
+    pub max_hp: u16,
+    /// 年齢 (年)。
+    pub age: u16,
+    pub status: CharacterStatus,
+    pub poisoned: bool,
+    /// 装備中のアイテム (アイテム種別ごとに高々 1 個)。
+    pub equipment: Vec<EquippedItem>,
+}
+
+impl Character {
+    /// 指定した種別の装備中アイテムを返す。
+    pub fn equipped(&self, kind: ItemKind) -> Option<&EquippedItem> {
+        self.equipment
+            .iter()
+            .find(|equipped| equipped.item.kind == kind)
+    }
+
+    /// 装備中の武器を返す。
+    pub fn weapon(&self) -> Option<&Item> {
+        self.equipped(ItemKind::Weapon)
+            .map(|equipped| &equipped.item)
+    }
+
+    fn items(&self) -> impl Iterator<Item = &Item> + '_ {
+        self.equipment.iter().map(|equipped| &equipped.item)
+    }
+
+    /// 指定したアイテムを装備できるかどうかを返す。装備できない場合はその理由を返す。
+    ///
+    /// 性格が合わないアイテムは装備できるが、呪われた状態になる (`equip` を参照)。
+    pub fn can_equip(&self, item: &Item) -> Result<(), EquipError> {
+        if !self.status.can_act() {
+            return Err(EquipError::Incapacitated(self.status));
+        }
+        if item.kind == ItemKind::Tool {
+            return Err(EquipError::NotEquippable);
+        }
+        if !item.equip_classes.contains(self.class) {
+            return Err(EquipError::ClassNotAllowed(self.class));
+        }
+        if self
+            .equipped(item.kind)
+            .is_some_and(|equipped| equipped.cursed)
+        {
+            return Err(EquipError::SlotCursed(item.kind));
+        }
+
+        Ok(())
+    }
+
+    /// アイテムを装備する。同じ種別のアイテムを装備していた場合はそれと入れ替える。
+    ///
+    /// 呪われたアイテム、および性格が合わない性格限定装備は呪われた状態で装備され、外せなくなる。
+    pub fn equip(&mut self, item_id: u8, item: Item) -> Result<EquipOutcome, EquipError> {
+        self.can_equip(&item)?;
+
+        let alignment_mismatch = item
+            .alignment
+            .is_some_and(|alignment| alignment != self.alignment);
+        let cursed = item.cursed || alignment_mismatch;
+
+        let replaced = self.take_equipped(item.kind);
+        self.equipment.push(EquippedItem {
+            item_id,
+            item,
+            cursed,
+        });
+
+        Ok(EquipOutcome {
+            replaced,
+            cursed,
+            alignment_mismatch,
+        })
+    }
+
+    /// 指定した種別の装備を外す。呪われている場合は外せない。
+    pub fn unequip(&mut self, kind: ItemKind) -> Result<Option<EquippedItem>, EquipError> {
+        if self.equipped(kind).is_some_and(|equipped| equipped.cursed) {
+            return Err(EquipError::SlotCursed(kind));
+        }
+
+        Ok(self.take_equipped(kind))
+    }
+
+    fn take_equipped(&mut self, kind: ItemKind) -> Option<EquippedItem> {
+        let idx = self
+            .equipment
+            .iter()
+            .position(|equipped| equipped.item.kind == kind)?;
+
+        Some(self.equipment.remove(idx))
+    }
+
+    /// AC を返す。
+    ///
+    /// 各装備の `ac` が基本値から減算される。
+    pub fn ac(&self) -> i32 {
+        BASE_AC - self.items().map(|item| i32::from(item.ac)).sum::<i32>()
+    }
+
+    /// 力による命中およびダメージの補正値を返す。
+    pub fn strength_bonus(&self) -> i32 {
+        let strength = i32::from(self.stats.get(Stat::Strength));
+
+        if strength >= 16 {
+            strength - 15
+        } else if strength < 6 {
+            strength - 6
+        } else {
+            0
+        }
+    }
+
+    /// 打撃の命中補正値を返す。
+    ///
+    /// 職業・レベルによる基本値、力による補正、各装備の `melee_accuracy` の和。
+    pub fn melee_hit_bonus(&self) -> i32 {
+        let level = i32::try_from(self.level).unwrap_or(i32::MAX);
+        let class_bonus = if is_fighter_class(self.class) || self.class == Class::Cleric {
+            2 + level / 3
+        } else {
+            level / 5
+        };
+        let item_bonus: i32 = self
+            .items()
+            .map(|item| i32::from(item.melee_accuracy))
+            .sum();
+
+        class_bonus + self.strength_bonus() + item_bonus
+    }
+
+    /// 打撃ダメージのダイス式を返す (武器を装備していなければ素手のもの)。
+    pub fn damage_dice_expr(&self) -> ItemMeleeDiceExpr {
+        self.weapon()
+            .map_or(UNARMED_DICE_EXPR, |weapon| weapon.melee_dice_expr)
+    }
+
+    /// ダイス式に加えられるダメージ補正値を返す (力による補正)。
+    pub fn damage_bonus(&self) -> i32 {
+        self.strength_bonus()
+    }
+
+    /// 1 ターンあたりの打撃回数を返す。
+    ///
+    /// 戦士系職業はレベルに応じて回数が増え、各装備の `extra_melee_count` が加算される。
+    pub fn swing_count(&self) -> u32 {
+        let class_count = if is_fighter_class(self.class) {
+            1 + self.level / 5
+        } else {
+            1
+        };
+        let extra: u32 = self
+            .items()
+            .map(|item| u32::from(item.extra_melee_count))
+            .sum();
+
+        (class_count + extra).min(SWING_COUNT_MAX)
+    }
+
+    /// 打撃がクリティカル (首はね) を起こしうるかどうかを返す。
+    ///
+    /// 忍者は常に、その他の職業は `critical` 属性の装備があるときのみ。
+    pub fn critical(&self) -> bool {
+        self.class == Class::Ninja || self.items().any(|item| item.critical)
+    }
+
+    /// 装備による属性抵抗マスクを返す。
+    pub fn element_resistance(&self) -> Elements {
+        self.items().fold(Elements::default(), |acc, item| {
+            acc | item.element_resistance
+        })
+    }
+
+    /// 1 ターンあたりの HP 回復量を返す (毒によるダメージを含む)。
+    pub fn healing_per_turn(&self) -> i32 {
+        let item_healing: i32 = self.items().map(|item| i32::from(item.healing)).sum();
+
+        item_healing - i32::from(self.poisoned)
+    }
+}
+
+/// 戦士系職業 (打撃回数がレベルに応じて増える職業) かどうかを返す。
+fn is_fighter_class(class: Class) -> bool {
+    matches!(
+        class,
+        Class::Fighter | Class::Samurai | Class::Lord | Class::Ninja
+    )
+}
+
+/// 装備中のアイテム。
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct EquippedItem {
+    pub item_id: u8,
+    pub item: Item,
+    /// 呪われていて外せない状態かどうか。
+    pub cursed: bool,
+}
+
+/// `Character::equip` の結果。
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct EquipOutcome {
+    /// 入れ替えで外れたアイテム。
+    pub replaced: Option<EquippedItem>,
+    /// 装備したアイテムが呪われた状態になったかどうか。
+    pub cursed: bool,
+    /// 性格が合わない性格限定装備だったかどうか。
+    pub alignment_mismatch: bool,
+}
+
+/// アイテムを装備できない、または外せない理由。
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum EquipError {
+    /// 行動できない状態のキャラクター。
+    Incapacitated(CharacterStatus),
+    /// 装備品でないアイテム (道具)。
+    NotEquippable,
+    /// 装備できない職業。
+    ClassNotAllowed(Class),
+    /// 同じ種別の呪われたアイテムを装備している。
+    SlotCursed(ItemKind),
+}
+
+impl std::fmt::Display for EquipError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Incapacitated(status) => {
+                write!(f, "character cannot act (status: {})", status.name())
+            }
+            Self::NotEquippable => f.write_str("item is not equippable"),
+            Self::ClassNotAllowed(class) => write!(f, "class {} cannot equip it", class.name()),
+            Self::SlotCursed(kind) => write!(f, "cursed {} is equipped", kind.name()),
+        }
+    }
+}
+
+impl std::error::Error for EquipError {}
+
+/// キャラクターの状態。
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub enum CharacterStatus {
+    #[default]
+    Ok,
+    Afraid,
+    Asleep,
+    Paralyzed,
+    Petrified,
+    Dead,
+    Ashed,
+    Lost,
+}
+
+impl CharacterStatus {
+    /// 正式名称を返す。
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Ok => "OK",
+            Self::Afraid => "恐怖",
+            Self::Asleep => "睡眠",
+            Self::Paralyzed => "麻痺",
+            Self::Petrified => "石化",
+            Self::Dead => "死",
+            Self::Ashed => "灰",
+            Self::Lost => "消滅",
+        }
+    }
+
+    /// 行動可能な状態かどうかを返す。
+    pub fn can_act(self) -> bool {
+        matches!(self, Self::Ok | Self::Afraid)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::element::Element;
+    use crate::test_util::{dummy_character, dummy_item};
+
+    use super::*;
+
+    #[test]
+    fn test_derived_values() {
+        let mut chara = dummy_character(Class::Fighter);
+
+        assert_eq!(chara.ac(), 10);
+        assert_eq!(chara.melee_hit_bonus(), 2 + 3 + 3);
+        assert_eq!(chara.damage_dice_expr(), UNARMED_DICE_EXPR);
+        assert_eq!(chara.swing_count(), 3);
+        assert!(!chara.critical());
+
+        let mut weapon = dummy_item(ItemKind::Weapon);
+        weapon.melee_dice_expr = ItemMeleeDiceExpr::new(1, 8, 0);
+        weapon.melee_accuracy = 2;
+        weapon.critical = true;
+        let mut armor = dummy_item(ItemKind::Armor);
+        armor.ac = 5;
+        armor.healing = 1;
+        armor.element_resistance = Element::Fire.into();
+
+        for (item_id, item) in [(1, weapon), (2, armor)] {
+            chara.equipment.push(EquippedItem {
+                item_id,
+                item,
+                cursed: false,
+            });
+        }
+        chara.poisoned = true;
+
+        assert_eq!(chara.ac(), 5);
+        assert_eq!(chara.melee_hit_bonus(), 2 + 3 + 3 + 2);
+        assert_eq!(chara.damage_dice_expr(), ItemMeleeDiceExpr::new(1, 8, 0));
+        assert!(chara.critical());
+        assert_eq!(chara.element_resistance(), Elements::from(Element::Fire));
+        assert_eq!(chara.healing_per_turn(), 0);
+    }
+
+    #[test]
+    fn test_equip() {
+        let mut chara = dummy_character(Class::Mage);
+
+        let mut tool = dummy_item(ItemKind::Tool);
+        assert_eq!(chara.can_equip(&tool), Err(EquipError::NotEquippable));
+        tool.kind = ItemKind::Weapon;
+        tool.equip_classes = Class::Fighter.into();
+        assert_eq!(
+            chara.can_equip(&tool),
+            Err(EquipError::ClassNotAllowed(Class::Mage))
+        );
+
+        let outcome = chara.equip(1, dummy_item(ItemKind::Armor)).unwrap();
+        assert!(outcome.replaced.is_none() && !outcome.cursed);
+
+        let mut evil_armor = dummy_item(ItemKind::Armor);
+        evil_armor.alignment = Some(Alignment::Evil);
+        let outcome = chara.equip(2, evil_armor).unwrap();
+        assert_eq!(outcome.replaced.unwrap().item_id, 1);
+        assert!(outcome.cursed && outcome.alignment_mismatch);
+        assert_eq!(chara.equipment.len(), 1);
+
+        assert_eq!(
+            chara.unequip(ItemKind::Armor),
+            Err(EquipError::SlotCursed(ItemKind::Armor))
+        );
+        assert_eq!(
+            chara.can_equip(&dummy_item(ItemKind::Armor)),
+            Err(EquipError::SlotCursed(ItemKind::Armor))
+        );
+        assert!(chara.unequip(ItemKind::Helm).unwrap().is_none());
+    }
+}
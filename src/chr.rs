@@ -0,0 +1,122 @@
+use crate::image::{IndexedImage, Rgb};
+
+/// 1 タイルあたりのバイト数。
+pub const TILE_LEN: usize = 16;
+
+pub const TILE_WIDTH: usize = 8;
+pub const TILE_HEIGHT: usize = 8;
+
+/// CHR データを確認するためのグレースケールのサブパレット。
+pub const CHR_GRAYSCALE: [Rgb; 4] = [
+    Rgb::from_u32(0x000000),
+    Rgb::from_u32(0x555555),
+    Rgb::from_u32(0xAAAAAA),
+    Rgb::from_u32(0xFFFFFF),
+];
+
+/// CHR タイル (8x8 ピクセル、各画素は `0..=3`)。
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct Tile([[u8; TILE_WIDTH]; TILE_HEIGHT]);
+
+impl Tile {
+    /// 2bpp planar 形式の 16 バイトをデコードする。
+    ///
+    /// 前半 8 バイトが下位ビット面、後半 8 バイトが上位ビット面で、各バイトの MSB が左端の画素。
+    pub fn decode(buf: &[u8; TILE_LEN]) -> Self {
+        let (lo, hi) = buf.split_at(8);
+
+        let rows = std::array::from_fn(|y| {
+            std::array::from_fn(|x| {
+                let shift = 7 - x;
+                let b0 = (lo[y] >> shift) & 1;
+                let b1 = (hi[y] >> shift) & 1;
+                b0 | (b1 << 1)
+            })
+        });
+
+        Self(rows)
+    }
+
+    pub fn pixel(&self, x: usize, y: usize) -> u8 {
+        self.0[y][x]
+    }
+
+    /// 左右反転したタイルを返す。
+    pub fn flip_h(&self) -> Self {
+        let mut rows = self.0;
+        for row in &mut rows {
+            row.reverse();
+        }
+
+        Self(rows)
+    }
+
+    /// 上下反転したタイルを返す。
+    pub fn flip_v(&self) -> Self {
+        let mut rows = self.0;
+        rows.reverse();
+
+        Self(rows)
+    }
+}
+
+/// CHR データをタイル列としてデコードする。
+///
+/// `buf` の長さは `TILE_LEN` の倍数でなければならない。
+pub fn decode_tiles(buf: &[u8]) -> Vec<Tile> {
+    assert_eq!(buf.len() % TILE_LEN, 0);
+
+    buf.chunks_exact(TILE_LEN)
+        .map(|chunk| Tile::decode(chunk.try_into().unwrap()))
+        .collect()
+}
+
+/// タイル列を 1 行あたり `columns` 個ずつ並べた画像を作る。
+pub fn render_tile_sheet(tiles: &[Tile], columns: usize) -> IndexedImage {
+    assert!(columns > 0);
+
+    let rows = tiles.len().div_ceil(columns);
+    let mut image = IndexedImage::new(TILE_WIDTH * columns, TILE_HEIGHT * rows);
+
+    for (i, tile) in tiles.iter().enumerate() {
+        let (col, row) = (i % columns, i / columns);
+        image.draw_tile(tile, TILE_WIDTH * col, TILE_HEIGHT * row, 0);
+    }
+
+    image
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tile_decode() {
+        let mut buf = [0; TILE_LEN];
+        buf[0] = 0b1000_0001;
+        buf[8] = 0b1100_0000;
+        buf[7] = 0b0000_0001;
+        buf[15] = 0b0000_0001;
+
+        let tile = Tile::decode(&buf);
+
+        assert_eq!(tile.pixel(0, 0), 3);
+        assert_eq!(tile.pixel(1, 0), 2);
+        assert_eq!(tile.pixel(2, 0), 0);
+        assert_eq!(tile.pixel(7, 0), 1);
+        assert_eq!(tile.pixel(7, 7), 3);
+
+        assert_eq!(tile.flip_h().pixel(0, 0), 1);
+        assert_eq!(tile.flip_v().pixel(7, 0), 3);
+    }
+
+    #[test]
+    fn test_render_tile_sheet() {
+        let tiles = decode_tiles(&[0xFF; 3 * TILE_LEN]);
+        let image = render_tile_sheet(&tiles, 2);
+
+        assert_eq!((image.width(), image.height()), (16, 16));
+        assert_eq!(image.pixel(8, 8), 0);
+        assert_eq!(image.pixel(7, 15), 3);
+    }
+}
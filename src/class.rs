@@ -2,6 +2,9 @@ use std::fmt::Write as _;
 
 use flagset::{flags, FlagSet};
 
+use crate::alignment::{Alignment, Alignments};
+use crate::stat::Stats;
+
 flags! {
     /// 職業。
     #[repr(u8)]
@@ -91,6 +94,51 @@ impl Class {
         ]
         .into_iter()
     }
+
+    /// 職業の就任条件を返す。
+    ///
+    /// NOTE: 取扱説明書などで知られている条件であり、ROM から抽出したものではない
+    /// (ROM 内のテーブルの位置が未特定のため)。
+    pub fn requirement(self) -> ClassRequirement {
+        use Alignment::*;
+
+        let (min_stats, alignments) = match self {
+            Self::Fighter => ([11, 0, 0, 0, 0, 0], Good | Neutral | Evil),
+            Self::Mage => ([0, 11, 0, 0, 0, 0], Good | Neutral | Evil),
+            Self::Cleric => ([0, 0, 11, 0, 0, 0], Good | Evil),
+            Self::Thief => ([0, 0, 0, 0, 11, 0], Neutral | Evil),
+            Self::Wizard => ([0, 12, 12, 0, 0, 0], Good | Evil),
+            Self::Samurai => ([15, 11, 10, 14, 10, 0], Good | Neutral),
+            Self::Lord => ([15, 12, 12, 15, 14, 15], Good.into()),
+            Self::Ninja => ([17; 6], Evil.into()),
+        };
+
+        ClassRequirement {
+            min_stats: Stats::new(min_stats),
+            alignments,
+        }
+    }
+
+    /// 能力値と性格がこの職業の就任条件を満たすかどうかを返す。
+    pub fn eligible(self, stats: Stats, alignment: Alignment) -> bool {
+        self.requirement().is_satisfied(stats, alignment)
+    }
+}
+
+/// 職業の就任条件。
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct ClassRequirement {
+    /// 必要な能力値の最小値。
+    pub min_stats: Stats,
+    /// 就任可能な性格。
+    pub alignments: Alignments,
+}
+
+impl ClassRequirement {
+    /// 能力値と性格がこの条件を満たすかどうかを返す。
+    pub fn is_satisfied(&self, stats: Stats, alignment: Alignment) -> bool {
+        stats.all_ge(self.min_stats) && self.alignments.contains(alignment)
+    }
 }
 
 /// 職業マスク。
@@ -153,3 +201,32 @@ impl std::fmt::Display for ClassesDisplayInitialPad {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_class_requirement() {
+        let req = ClassRequirement {
+            min_stats: Stats::new([15, 12, 12, 15, 14, 15]),
+            alignments: Alignment::Good.into(),
+        };
+
+        let stats = Stats::new([15, 12, 12, 15, 14, 15]);
+        assert!(req.is_satisfied(stats, Alignment::Good));
+        assert!(!req.is_satisfied(stats, Alignment::Neutral));
+        assert!(!req.is_satisfied(Stats::new([15, 12, 12, 15, 13, 18]), Alignment::Good));
+    }
+
+    #[test]
+    fn test_eligible() {
+        let stats = Stats::new([15, 12, 12, 15, 14, 15]);
+        assert!(Class::Lord.eligible(stats, Alignment::Good));
+        assert!(!Class::Lord.eligible(stats, Alignment::Neutral));
+        assert!(!Class::Ninja.eligible(stats, Alignment::Evil));
+        assert!(Class::Ninja.eligible(Stats::new([17; 6]), Alignment::Evil));
+        assert!(!Class::Thief.eligible(stats, Alignment::Good));
+        assert!(Class::Fighter.eligible(Stats::new([11, 3, 3, 3, 3, 3]), Alignment::Neutral));
+    }
+}
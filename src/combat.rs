@@ -0,0 +1,210 @@
+use crate::character::Character;
+use crate::distribution::{dice_distribution, ValueDistribution};
+use crate::item::ItemMeleeDiceExpr;
+use crate::monster::{Monster, MonsterKinds};
+use crate::rng::{gen_range_distribution, percent_probability};
+
+/// クリティカル (首はね) 発生率の上限 (%)。
+pub const CRITICAL_PERCENT_MAX: u8 = 50;
+
+/// キャラクターの打撃のパラメータ。
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct MeleeAttack {
+    /// 命中補正値。
+    pub hit_bonus: i32,
+    /// 1 ターンあたりの打撃回数。
+    pub swing_count: u32,
+    pub dice_expr: ItemMeleeDiceExpr,
+    /// ダイス式に加えられるダメージ補正値。
+    pub damage_bonus: i32,
+    /// 命中した打撃がクリティカルとなる確率 (%)。
+    pub critical_percent: u8,
+    /// 倍打対象のモンスター種別マスク。
+    pub slay_monster_kinds: MonsterKinds,
+}
+
+impl MeleeAttack {
+    /// キャラクターの能力値と装備から打撃のパラメータを求める。
+    ///
+    /// クリティカル発生率はレベルの 2 倍 (%) で、`CRITICAL_PERCENT_MAX` を上限とする。
+    pub fn from_character(chara: &Character) -> Self {
+        let critical_percent = if chara.critical() {
+            u8::try_from(chara.level.saturating_mul(2))
+                .unwrap_or(u8::MAX)
+                .min(CRITICAL_PERCENT_MAX)
+        } else {
+            0
+        };
+        let slay_monster_kinds = chara
+            .equipment
+            .iter()
+            .fold(MonsterKinds::default(), |acc, equipped| {
+                acc | equipped.item.slay_monster_kinds
+            });
+
+        Self {
+            hit_bonus: chara.melee_hit_bonus(),
+            swing_count: chara.swing_count(),
+            dice_expr: chara.damage_dice_expr(),
+            damage_bonus: chara.damage_bonus(),
+            critical_percent,
+            slay_monster_kinds,
+        }
+    }
+}
+
+/// 1 ターン分の打撃をモンスターに行った結果の分布。
+#[derive(Clone, Debug, PartialEq)]
+pub struct MeleeOutcome {
+    /// 1 回の打撃の命中率。
+    pub hit_probability: f64,
+    /// 打撃回数。
+    pub swing_count: u32,
+    /// 倍打が適用されるかどうか。
+    pub slay: bool,
+    /// 命中した打撃 1 回のダメージ分布。
+    pub damage_per_hit: ValueDistribution,
+    /// 1 ターンの合計ダメージ分布 (クリティカルを考慮しない)。
+    pub damage: ValueDistribution,
+    /// 1 ターンに 1 回以上クリティカルが発生する確率。
+    pub critical_probability: f64,
+    /// クリティカルが発生しなかった場合の合計ダメージの部分分布。
+    damage_without_critical: ValueDistribution,
+}
+
+impl MeleeOutcome {
+    /// HP が `hp` のモンスターを 1 ターンで倒す確率を返す (クリティカルによる即死を含む)。
+    pub fn kill_probability(&self, hp: u32) -> f64 {
+        let hp = usize::try_from(hp).unwrap();
+
+        1.0 - self.damage_without_critical.probability_less_than(hp)
+    }
+
+    /// HP の分布が `hp` であるモンスターを 1 ターンで倒す確率を返す。
+    pub fn kill_probability_dist(&self, hp: &ValueDistribution) -> f64 {
+        hp.probs()
+            .iter()
+            .enumerate()
+            .map(|(value, p)| p * self.kill_probability(u32::try_from(value).unwrap()))
+            .sum()
+    }
+}
+
+/// 1 回の打撃が命中する確率を返す。
+///
+/// `gen_range(20) + 命中補正値 + 対象の AC >= 19` のとき命中するものとする。
+///
+/// NOTE: 原作の命中判定ルーチンは未解析で、これは Apple II 版の判定式と同じであると仮定したもの
+/// (未検証)。命中補正値 ([`Character::melee_hit_bonus`]) の式も同様。
+pub fn melee_hit_probability(hit_bonus: i32, target_ac: i32) -> f64 {
+    let need = 19 - hit_bonus - target_ac;
+
+    gen_range_distribution(20)
+        .into_iter()
+        .zip(0..)
+        .filter(|&(_, roll)| roll >= need)
+        .map(|(p, _)| p)
+        .sum()
+}
+
+/// キャラクターの打撃をモンスターに対して 1 ターン行った結果の分布を求める。
+///
+/// 倍打対象の種別を持つモンスターにはダメージが 2 倍になる。
+/// クリティカルが発生するとモンスターは即死する。
+pub fn melee_against_monster(attack: &MeleeAttack, monster: &Monster) -> MeleeOutcome {
+    melee_against_target(attack, i32::from(monster.ac), monster.kinds)
+}
+
+/// キャラクターの打撃を、AC が `target_ac`、種別が `target_kinds` の相手に対して 1 ターン行った結果の
+/// 分布を求める。
+///
+/// [`melee_against_monster`] と同じだが、相手をモンスターデータではなく AC と種別で指定する。
+pub fn melee_against_target(
+    attack: &MeleeAttack,
+    target_ac: i32,
+    target_kinds: MonsterKinds,
+) -> MeleeOutcome {
+    let hit_probability = melee_hit_probability(attack.hit_bonus, target_ac);
+    let slay = !(attack.slay_monster_kinds & target_kinds).is_empty();
+
+    let dice = attack.dice_expr;
+    let mut damage_per_hit = dice_distribution(
+        dice.count(),
+        dice.face(),
+        i32::from(dice.bias_decoded()) + attack.damage_bonus,
+        1,
+    );
+    if slay {
+        damage_per_hit = damage_per_hit.map(|value| 2 * value);
+    }
+
+    let miss = ValueDistribution::constant(0).scale(1.0 - hit_probability);
+    let swing = miss.merge(&damage_per_hit.scale(hit_probability));
+    let damage = swing.convolve_pow(attack.swing_count);
+
+    let p_critical = percent_probability(attack.critical_percent);
+    let swing_without_critical =
+        miss.merge(&damage_per_hit.scale(hit_probability * (1.0 - p_critical)));
+    let damage_without_critical = swing_without_critical.convolve_pow(attack.swing_count);
+    let critical_probability =
+        1.0 - (1.0 - hit_probability * p_critical).powi(i32::try_from(attack.swing_count).unwrap());
+
+    MeleeOutcome {
+        hit_probability,
+        swing_count: attack.swing_count,
+        slay,
+        damage_per_hit,
+        damage,
+        critical_probability,
+        damage_without_critical,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::monster::MonsterKind;
+    use crate::test_util::dummy_monster;
+
+    use super::*;
+
+    #[test]
+    fn test_melee_hit_probability() {
+        assert_eq!(melee_hit_probability(0, 19), 1.0);
+        assert_eq!(melee_hit_probability(0, -1), 0.0);
+        // gen_range(20) の 10..=19 は 128/256。
+        assert_eq!(melee_hit_probability(5, 4), 128.0 / 256.0);
+    }
+
+    #[test]
+    fn test_melee_against_monster() {
+        let attack = MeleeAttack {
+            hit_bonus: 100,
+            swing_count: 2,
+            dice_expr: ItemMeleeDiceExpr::new(1, 1, 0),
+            damage_bonus: 0,
+            critical_percent: 0,
+            slay_monster_kinds: MonsterKind::Dragon.into(),
+        };
+        let mut monster = dummy_monster();
+
+        let outcome = melee_against_monster(&attack, &monster);
+        assert_eq!(outcome.hit_probability, 1.0);
+        assert_eq!(outcome.damage.probability(2), 1.0);
+        assert_eq!(outcome.kill_probability(2), 1.0);
+        assert_eq!(outcome.kill_probability(3), 0.0);
+
+        monster.kinds = MonsterKind::Dragon.into();
+        let outcome = melee_against_monster(&attack, &monster);
+        assert!(outcome.slay);
+        assert_eq!(outcome.damage.probability(4), 1.0);
+
+        let attack = MeleeAttack {
+            critical_percent: 50,
+            ..attack
+        };
+        let outcome = melee_against_monster(&attack, &monster);
+        let p = percent_probability(50);
+        assert!((outcome.critical_probability - (1.0 - (1.0 - p).powi(2))).abs() < 1e-9);
+        assert!((outcome.kill_probability(100) - outcome.critical_probability).abs() < 1e-9);
+    }
+}
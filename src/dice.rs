@@ -11,19 +11,19 @@ macro_rules! define_dice_expr {
         }
 
         impl $name {
-            pub fn new(count: u8, face: u8, bias: u8) -> Self {
+            pub const fn new(count: u8, face: u8, bias: u8) -> Self {
                 Self { count, face, bias }
             }
 
-            pub fn count(self) -> u8 {
+            pub const fn count(self) -> u8 {
                 self.count
             }
 
-            pub fn face(self) -> u8 {
+            pub const fn face(self) -> u8 {
                 self.face
             }
 
-            pub fn bias(self) -> u8 {
+            pub const fn bias(self) -> u8 {
                 self.bias
             }
         }
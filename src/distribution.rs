@@ -0,0 +1,147 @@
+use crate::rng::gen_range_distribution;
+
+/// 非負整数値の離散確率分布。
+///
+/// `i` 番目の要素が値 `i` をとる確率。確率の総和が 1 未満の「部分分布」も扱える
+/// (例えば「クリティカルが起きなかった場合のダメージ」など)。
+#[derive(Clone, Debug, PartialEq)]
+pub struct ValueDistribution {
+    probs: Vec<f64>,
+}
+
+impl ValueDistribution {
+    /// 各値の確率の列から分布を作る。
+    pub fn from_probs(probs: Vec<f64>) -> Self {
+        Self { probs }
+    }
+
+    /// 常に指定した値をとる分布を返す。
+    pub fn constant(value: usize) -> Self {
+        let mut probs = vec![0.0; value + 1];
+        probs[value] = 1.0;
+
+        Self { probs }
+    }
+
+    /// 各値の確率の列を返す。
+    pub fn probs(&self) -> &[f64] {
+        &self.probs
+    }
+
+    /// 値 `value` をとる確率を返す。
+    pub fn probability(&self, value: usize) -> f64 {
+        self.probs.get(value).copied().unwrap_or(0.0)
+    }
+
+    /// 確率の総和を返す。
+    pub fn total(&self) -> f64 {
+        self.probs.iter().sum()
+    }
+
+    /// とりうる最大値を返す (確率が全て 0 なら 0)。
+    pub fn max_value(&self) -> usize {
+        self.probs.iter().rposition(|&p| p > 0.0).unwrap_or(0)
+    }
+
+    /// 期待値を返す (部分分布の場合、正規化しない値)。
+    pub fn mean(&self) -> f64 {
+        self.probs
+            .iter()
+            .enumerate()
+            .map(|(value, p)| value as f64 * p)
+            .sum()
+    }
+
+    /// 値が `value` 未満となる確率を返す。
+    pub fn probability_less_than(&self, value: usize) -> f64 {
+        self.probs.iter().take(value).sum()
+    }
+
+    /// 値が `value` 以上となる確率を返す。
+    pub fn probability_at_least(&self, value: usize) -> f64 {
+        self.probs.iter().skip(value).sum()
+    }
+
+    /// 各値の確率を `factor` 倍した分布を返す。
+    pub fn scale(&self, factor: f64) -> Self {
+        Self::from_probs(self.probs.iter().map(|p| p * factor).collect())
+    }
+
+    /// 2 つの部分分布の確率を値ごとに足し合わせた分布を返す (排反な事象の合併)。
+    pub fn merge(&self, other: &Self) -> Self {
+        let len = self.probs.len().max(other.probs.len());
+        let probs = (0..len)
+            .map(|value| self.probability(value) + other.probability(value))
+            .collect();
+
+        Self::from_probs(probs)
+    }
+
+    /// 各値を `f` で写した分布を返す。
+    pub fn map(&self, f: impl Fn(usize) -> usize) -> Self {
+        let mut probs = Vec::<f64>::new();
+        for (value, &p) in self.probs.iter().enumerate() {
+            let mapped = f(value);
+            if probs.len() <= mapped {
+                probs.resize(mapped + 1, 0.0);
+            }
+            probs[mapped] += p;
+        }
+
+        Self::from_probs(probs)
+    }
+
+    /// 独立な 2 つの値の和の分布を返す。
+    pub fn convolve(&self, other: &Self) -> Self {
+        if self.probs.is_empty() || other.probs.is_empty() {
+            return Self::from_probs(vec![]);
+        }
+
+        let mut probs = vec![0.0; self.probs.len() + other.probs.len() - 1];
+        for (i, &p) in self.probs.iter().enumerate() {
+            if p == 0.0 {
+                continue;
+            }
+            for (j, &q) in other.probs.iter().enumerate() {
+                probs[i + j] += p * q;
+            }
+        }
+
+        Self::from_probs(probs)
+    }
+
+    /// この分布に独立に従う `n` 個の値の和の分布を返す。
+    pub fn convolve_pow(&self, n: u32) -> Self {
+        (0..n).fold(Self::constant(0), |acc, _| acc.convolve(self))
+    }
+}
+
+/// 原作の乱数生成器でダイスを振った結果に `bias` を加えた値の分布を返す。
+///
+/// 1 個のダイスの出目は `gen_range(face) + 1`。結果は `min` 未満にはならない。
+pub fn dice_distribution(count: u8, face: u8, bias: i32, min: usize) -> ValueDistribution {
+    let die = ValueDistribution::from_probs(gen_range_distribution(face)).map(|value| value + 1);
+    let sum = die.convolve_pow(u32::from(count));
+
+    let min = i64::try_from(min).unwrap();
+    sum.map(|value| {
+        let value = i64::try_from(value).unwrap() + i64::from(bias);
+        usize::try_from(value.max(min)).unwrap()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_dice_distribution() {
+        let dist = dice_distribution(2, 4, -3, 1);
+
+        assert!((dist.total() - 1.0).abs() < 1e-9);
+        assert_eq!(dist.max_value(), 5);
+        // 2d4 で 2, 3, 4 が出ると 1 に切り上げられる。
+        assert!((dist.probability(1) - 6.0 / 16.0).abs() < 1e-9);
+        assert!((dist.mean() - 36.0 / 16.0).abs() < 1e-9);
+    }
+}
@@ -0,0 +1,325 @@
+use flagset::{flags, FlagSet};
+
+use crate::dice::define_dice_expr;
+use crate::rng::{percent_probability, stepped_value_distribution};
+
+/// ドロップテーブル (戦闘後の報酬)。
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DropTable {
+    /// 宝箱が出るかどうか。
+    pub chest: bool,
+    /// 宝箱に仕掛けられうる罠の種別マスク。
+    ///
+    /// 罠の確率は [`DropTable::trap_probabilities`] を参照。
+    pub trap_kinds: TrapKinds,
+    pub entries: Vec<DropEntry>,
+}
+
+impl DropTable {
+    /// 宝箱に各罠が仕掛けられている確率を、罠の種別の昇順で返す。宝箱が出ない場合は空。
+    ///
+    /// ドロップテーブルには罠の種別マスクしか無く、罠が仕掛けられる確率や種別の選び方は
+    /// 宝箱を開ける側の処理で決まる。その処理は未解析なので、罠が仕掛けられている確率
+    /// `trapped_probability` は呼び出し側が与え、種別は `trap_kinds` から一様に選ばれると仮定する。
+    pub fn trap_probabilities(&self, trapped_probability: f64) -> Vec<(TrapKind, f64)> {
+        assert!((0.0..=1.0).contains(&trapped_probability));
+
+        if !self.chest || self.trap_kinds.is_empty() {
+            return vec![];
+        }
+
+        let p = trapped_probability / self.trap_kinds.into_iter().count() as f64;
+
+        self.trap_kinds.into_iter().map(|kind| (kind, p)).collect()
+    }
+
+    /// 各アイテムがドロップする確率を、アイテムIDの昇順で返す。
+    ///
+    /// 各エントリは独立に判定されるので、複数のエントリに含まれるアイテムは
+    /// 「少なくとも 1 つのエントリで出る」確率になる。
+    pub fn item_probabilities(&self) -> Vec<(u8, f64)> {
+        let mut not_dropped = [1.0_f64; 256];
+
+        for entry in &self.entries {
+            let DropContent::Item(item) = entry.content else {
+                continue;
+            };
+            let p_entry = entry.probability();
+            for (id, p) in item.item_probabilities() {
+                not_dropped[usize::from(id)] *= 1.0 - p_entry * p;
+            }
+        }
+
+        not_dropped
+            .into_iter()
+            .enumerate()
+            .filter(|&(_, q)| q < 1.0)
+            .map(|(id, q)| (u8::try_from(id).unwrap(), 1.0 - q))
+            .collect()
+    }
+}
+
+/// ドロップテーブルのエントリ。
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct DropEntry {
+    /// このエントリが選ばれる確率 (%)。
+    pub percent: u8,
+    pub content: DropContent,
+}
+
+impl DropEntry {
+    /// このエントリが選ばれる確率を返す。
+    pub fn probability(self) -> f64 {
+        percent_probability(self.percent)
+    }
+}
+
+/// ドロップテーブルのエントリの内容。
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum DropContent {
+    Gold(GoldDrop),
+    Item(ItemDrop),
+}
+
+/// 金貨のドロップ。
+///
+/// 金額は `(ダイス式の値) * multiplier` となる。
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct GoldDrop {
+    pub dice_expr: GoldDiceExpr,
+    pub multiplier: u16,
+}
+
+impl std::fmt::Display for GoldDrop {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.multiplier == 1 {
+            write!(f, "{}", self.dice_expr)
+        } else {
+            write!(f, "({})*{}", self.dice_expr, self.multiplier)
+        }
+    }
+}
+
+// 金貨のドロップ額のダイス式。
+define_dice_expr!(GoldDiceExpr);
+
+impl std::fmt::Display for GoldDiceExpr {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let count = self.count;
+        let face = self.face;
+        let bias = self.bias;
+
+        if bias == 0 {
+            write!(f, "{count}d{face}")
+        } else {
+            write!(f, "{count}d{face}+{bias}")
+        }
+    }
+}
+
+/// アイテムのドロップ。
+///
+/// まず `item_id_min + (0..range の乱数)` でアイテムIDを決め、その後
+/// `max_step_count` 回を上限として `step_percent` % の確率でIDに `step` を加算し続ける。
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct ItemDrop {
+    pub item_id_min: u8,
+    pub range: u8,
+    pub step: u8,
+    pub max_step_count: u8,
+    pub step_percent: u8,
+}
+
+impl ItemDrop {
+    /// このエントリが選ばれたときの各アイテムIDの確率を、アイテムIDの昇順で返す。
+    pub fn item_probabilities(self) -> Vec<(u8, f64)> {
+        stepped_value_distribution(
+            self.item_id_min,
+            self.range,
+            self.step,
+            self.max_step_count,
+            self.step_percent,
+        )
+        .into_iter()
+        .filter_map(|(id, p)| u8::try_from(id).ok().map(|id| (id, p)))
+        .collect()
+    }
+}
+
+flags! {
+    /// 宝箱の罠の種別。
+    #[repr(u8)]
+    pub enum TrapKind: u8 {
+        /// 毒針。
+        PoisonNeedle = 1 << 0,
+        /// ガス爆弾。
+        GasBomb = 1 << 1,
+        /// 石弓の矢。
+        CrossbowBolt = 1 << 2,
+        /// 爆弾。
+        ExplodingBox = 1 << 3,
+        /// スタナー。
+        Stunner = 1 << 4,
+        /// テレポーター。
+        Teleporter = 1 << 5,
+        /// メイジブラスター。
+        MageBlaster = 1 << 6,
+        /// プリーストブラスター。
+        PriestBlaster = 1 << 7,
+    }
+}
+
+impl TrapKind {
+    /// 正式名称を返す。
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::PoisonNeedle => "毒針",
+            Self::GasBomb => "ガス爆弾",
+            Self::CrossbowBolt => "石弓の矢",
+            Self::ExplodingBox => "爆弾",
+            Self::Stunner => "スタナー",
+            Self::Teleporter => "テレポーター",
+            Self::MageBlaster => "メイジブラスター",
+            Self::PriestBlaster => "プリーストブラスター",
+        }
+    }
+
+    /// 全ての罠の種別を昇順で返す。
+    pub fn iter(
+    ) -> impl DoubleEndedIterator<Item = Self> + ExactSizeIterator + std::iter::FusedIterator + Clone
+    {
+        [
+            Self::PoisonNeedle,
+            Self::GasBomb,
+            Self::CrossbowBolt,
+            Self::ExplodingBox,
+            Self::Stunner,
+            Self::Teleporter,
+            Self::MageBlaster,
+            Self::PriestBlaster,
+        ]
+        .into_iter()
+    }
+}
+
+/// 罠の種別マスク。
+pub type TrapKinds = FlagSet<TrapKind>;
+
+/// 罠の種別マスクをフォーマットし、正式名称を join した文字列にする。
+#[derive(Debug)]
+pub struct TrapKindsDisplay<'sep> {
+    kinds: TrapKinds,
+    sep: &'sep str,
+}
+
+impl<'sep> TrapKindsDisplay<'sep> {
+    pub fn new(kinds: TrapKinds, sep: &'sep str) -> Self {
+        Self { kinds, sep }
+    }
+}
+
+impl std::fmt::Display for TrapKindsDisplay<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut first = true;
+        for kind in TrapKind::iter() {
+            if self.kinds.contains(kind) {
+                if !first {
+                    f.write_str(self.sep)?;
+                }
+                f.write_str(kind.name())?;
+                first = false;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn item_entry(percent: u8, item_id_min: u8, range: u8) -> DropEntry {
+        DropEntry {
+            percent,
+            content: DropContent::Item(ItemDrop {
+                item_id_min,
+                range,
+                step: 0,
+                max_step_count: 0,
+                step_percent: 0,
+            }),
+        }
+    }
+
+    #[test]
+    fn test_item_drop_item_probabilities() {
+        let drop = ItemDrop {
+            item_id_min: 10,
+            range: 2,
+            step: 5,
+            max_step_count: 1,
+            step_percent: 50,
+        };
+
+        let probs = drop.item_probabilities();
+
+        let p_step = percent_probability(50);
+        let expected = [
+            (10, 0.5 * (1.0 - p_step)),
+            (11, 0.5 * (1.0 - p_step)),
+            (15, 0.5 * p_step),
+            (16, 0.5 * p_step),
+        ];
+        assert_eq!(probs.len(), expected.len());
+        for ((id, p), (id_expected, p_expected)) in probs.into_iter().zip(expected) {
+            assert_eq!(id, id_expected);
+            assert!((p - p_expected).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn test_drop_table_item_probabilities() {
+        let table = DropTable {
+            chest: true,
+            trap_kinds: TrapKinds::default(),
+            entries: vec![
+                item_entry(100, 1, 1),
+                item_entry(50, 1, 2),
+                DropEntry {
+                    percent: 100,
+                    content: DropContent::Gold(GoldDrop {
+                        dice_expr: GoldDiceExpr::new(1, 6, 0),
+                        multiplier: 10,
+                    }),
+                },
+            ],
+        };
+
+        let probs = table.item_probabilities();
+
+        // アイテム 1 は 1 つ目のエントリで必ず出る
+        assert_eq!(probs[0], (1, 1.0));
+        // アイテム 2 は 2 つ目のエントリでのみ出る
+        assert_eq!(probs[1].0, 2);
+        assert!((probs[1].1 - 0.5 * percent_probability(50)).abs() < 1e-12);
+        assert_eq!(probs.len(), 2);
+    }
+
+    #[test]
+    fn test_trap_probabilities() {
+        let mut table = DropTable {
+            chest: true,
+            trap_kinds: TrapKind::PoisonNeedle | TrapKind::Teleporter,
+            entries: vec![],
+        };
+
+        assert_eq!(
+            table.trap_probabilities(0.5),
+            [(TrapKind::PoisonNeedle, 0.25), (TrapKind::Teleporter, 0.25)]
+        );
+
+        table.chest = false;
+        assert!(table.trap_probabilities(0.5).is_empty());
+    }
+}
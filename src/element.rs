@@ -132,3 +132,47 @@ impl std::fmt::Display for ElementsDisplayAbbrev<'_> {
         Ok(())
     }
 }
+
+/// 属性攻撃に対する抵抗の効果。
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum Resistance {
+    /// 抵抗しない。
+    None,
+    /// ダメージが半減する (キャラクターが装備で抵抗する場合)。
+    Halved,
+    /// ダメージを受けない (モンスターが抵抗する場合)。
+    Negated,
+}
+
+impl Resistance {
+    /// 攻撃属性と抵抗属性マスクから、キャラクターに対する抵抗の効果を求める。
+    ///
+    /// 攻撃属性のいずれかに抵抗していれば半減する。
+    pub fn for_character(attack_elements: Elements, resistance: Elements) -> Self {
+        if (attack_elements & resistance).is_empty() {
+            Self::None
+        } else {
+            Self::Halved
+        }
+    }
+
+    /// 攻撃属性と抵抗属性マスクから、モンスターに対する抵抗の効果を求める。
+    ///
+    /// 攻撃属性のいずれかに抵抗していれば無効化される。
+    pub fn for_monster(attack_elements: Elements, resistance: Elements) -> Self {
+        if (attack_elements & resistance).is_empty() {
+            Self::None
+        } else {
+            Self::Negated
+        }
+    }
+
+    /// 抵抗の効果を適用したダメージを返す。
+    pub fn apply(self, damage: u32) -> u32 {
+        match self {
+            Self::None => damage,
+            Self::Halved => damage / 2,
+            Self::Negated => 0,
+        }
+    }
+}
@@ -0,0 +1,177 @@
+use crate::rng::stepped_value_distribution;
+
+/// 1 フロア分の遭遇テーブル。
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct EncounterTable {
+    /// 徘徊モンスターの出現グループ (玄室の戦闘でも使われる)。
+    pub groups: [EncounterGroup; 3],
+    /// 玄室 (入ると必ず戦闘になるマス) とその番人。
+    pub rooms: Vec<RoomEncounter>,
+    /// 特殊マスによる固定の戦闘。
+    pub fixed_encounters: Vec<FixedEncounter>,
+}
+
+impl EncounterTable {
+    /// 各グループが選ばれる確率を返す。
+    pub fn group_probabilities(&self) -> [f64; 3] {
+        let total: u32 = self.groups.iter().map(|g| u32::from(g.weight)).sum();
+
+        self.groups.map(|g| {
+            if total == 0 {
+                0.0
+            } else {
+                f64::from(g.weight) / f64::from(total)
+            }
+        })
+    }
+
+    /// 1 回の遭遇で各モンスターが (先頭グループとして) 出現する確率を、モンスターIDの昇順で返す。
+    pub fn monster_probabilities(&self) -> Vec<(u8, f64)> {
+        let mut probs = [0.0_f64; 256];
+
+        for (group, p_group) in self.groups.iter().zip(self.group_probabilities()) {
+            for (id, p) in group.monster_probabilities() {
+                probs[usize::from(id)] += p_group * p;
+            }
+        }
+
+        probs
+            .into_iter()
+            .enumerate()
+            .filter(|&(_, p)| p > 0.0)
+            .map(|(id, p)| (u8::try_from(id).unwrap(), p))
+            .collect()
+    }
+
+    /// 指定した玄室の番人として各モンスターが (先頭グループとして) 出現する確率を、モンスターIDの昇順で返す。
+    pub fn guardian_probabilities(&self, room: &RoomEncounter) -> Vec<(u8, f64)> {
+        match room.guardian {
+            RoomGuardian::Fixed { monster_id } => vec![(monster_id, 1.0)],
+            RoomGuardian::Wandering => self.monster_probabilities(),
+        }
+    }
+}
+
+/// 徘徊モンスターの出現グループ。
+///
+/// モンスターIDは `monster_id_min + (0..range の乱数)` で決まり、その後
+/// `max_step_count` 回を上限として `step_percent` % の確率で `step` が加算され続ける。
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct EncounterGroup {
+    /// このグループが選ばれる重み。
+    pub weight: u8,
+    pub monster_id_min: u8,
+    pub range: u8,
+    pub step: u8,
+    pub max_step_count: u8,
+    pub step_percent: u8,
+}
+
+impl EncounterGroup {
+    /// このグループが選ばれたときの各モンスターIDの確率を、モンスターIDの昇順で返す。
+    pub fn monster_probabilities(self) -> Vec<(u8, f64)> {
+        stepped_value_distribution(
+            self.monster_id_min,
+            self.range,
+            self.step,
+            self.max_step_count,
+            self.step_percent,
+        )
+        .into_iter()
+        .filter_map(|(id, p)| u8::try_from(id).ok().map(|id| (id, p)))
+        .collect()
+    }
+}
+
+/// 玄室のマス。
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct RoomEncounter {
+    pub x: u8,
+    pub y: u8,
+    pub guardian: RoomGuardian,
+}
+
+/// 玄室の番人の決まり方。
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum RoomGuardian {
+    /// 固定の戦闘のマスであり、番人は指定したモンスター。
+    Fixed { monster_id: u8 },
+    /// 徘徊モンスターと同じく、フロアの出現グループから選ばれる。
+    Wandering,
+}
+
+/// 特殊マスによる固定の戦闘。
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct FixedEncounter {
+    pub x: u8,
+    pub y: u8,
+    pub monster_id: u8,
+    /// 残り発生回数 (負なら無制限)。
+    pub remaining: i16,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn group(weight: u8, monster_id_min: u8, range: u8) -> EncounterGroup {
+        EncounterGroup {
+            weight,
+            monster_id_min,
+            range,
+            step: 0,
+            max_step_count: 0,
+            step_percent: 0,
+        }
+    }
+
+    fn table(groups: [EncounterGroup; 3]) -> EncounterTable {
+        EncounterTable {
+            groups,
+            rooms: vec![],
+            fixed_encounters: vec![],
+        }
+    }
+
+    #[test]
+    fn test_group_probabilities() {
+        let table = table([group(1, 0, 1), group(3, 1, 1), group(0, 2, 1)]);
+
+        assert_eq!(table.group_probabilities(), [0.25, 0.75, 0.0]);
+    }
+
+    #[test]
+    fn test_monster_probabilities() {
+        // グループ 0 と 1 の両方にモンスター 1 が含まれる
+        let table = table([group(1, 0, 2), group(1, 1, 1), group(0, 5, 1)]);
+
+        assert_eq!(table.monster_probabilities(), [(0, 0.25), (1, 0.75)]);
+    }
+
+    #[test]
+    fn test_zero_weight() {
+        let table = table([group(0, 0, 1), group(0, 1, 1), group(0, 2, 1)]);
+
+        assert_eq!(table.group_probabilities(), [0.0; 3]);
+        assert!(table.monster_probabilities().is_empty());
+    }
+
+    #[test]
+    fn test_guardian_probabilities() {
+        let table = table([group(1, 3, 1), group(0, 0, 1), group(0, 0, 1)]);
+
+        let fixed = RoomEncounter {
+            x: 0,
+            y: 0,
+            guardian: RoomGuardian::Fixed { monster_id: 7 },
+        };
+        assert_eq!(table.guardian_probabilities(&fixed), [(7, 1.0)]);
+
+        let wandering = RoomEncounter {
+            x: 1,
+            y: 0,
+            guardian: RoomGuardian::Wandering,
+        };
+        assert_eq!(table.guardian_probabilities(&wandering), [(3, 1.0)]);
+    }
+}
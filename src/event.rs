@@ -0,0 +1,277 @@
+use std::fmt::Write as _;
+
+use crate::alignment::Alignments;
+use crate::maze::Position;
+use crate::string::GameString;
+
+/// 特殊マスのイベント。
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Event {
+    pub position: Position,
+    pub trigger: EventTrigger,
+    /// 残り発生回数 (`None` なら無制限)。
+    pub remaining: Option<u16>,
+    pub body: Vec<EventStmt>,
+}
+
+/// イベントの発生契機。
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum EventTrigger {
+    /// マスに入ったとき。
+    Enter,
+    /// マスを調べたとき。
+    Search,
+}
+
+/// イベントの文。
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum EventStmt {
+    /// メッセージを表示する (各要素が 1 行)。
+    Message(Vec<GameString>),
+    /// 条件分岐。
+    If {
+        cond: EventCond,
+        then: Vec<EventStmt>,
+        else_: Vec<EventStmt>,
+    },
+    /// アイテムを入手する。
+    GiveItem(u8),
+    /// モンスターと戦闘する。
+    Fight(u8),
+    /// 元のマスへ押し戻す。
+    PushBack,
+    /// 金貨を払う。
+    PayGold(u16),
+    /// 解釈できないイベント。原作のイベント種別と特殊マスの補助値をそのまま保持する。
+    Unknown { kind: u8, aux: [i16; 3] },
+}
+
+/// イベントの条件。
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum EventCond {
+    /// パーティの誰かが指定したアイテムを持っている。
+    HasItem(u8),
+    /// パーティ全員の性格が指定したマスクに含まれる。
+    Alignment(Alignments),
+    /// 入力した答えが一致する。
+    Answer(GameString),
+    /// 「はい」を選んだ。
+    Yes,
+}
+
+impl std::fmt::Display for Event {
+    /// アイテム/モンスターは ID のみで表示する。名前付きで表示するには [`EventDisplay`] を使う。
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        EventDisplay::new(self, &[], &[]).fmt(f)
+    }
+}
+
+/// イベントを、アイテム/モンスターの名前付きの疑似コードとしてフォーマットする。
+///
+/// 名前テーブルの範囲外の ID は `#{id}` とだけ表示する。
+#[derive(Debug)]
+pub struct EventDisplay<'a> {
+    event: &'a Event,
+    names: Names<'a>,
+}
+
+impl<'a> EventDisplay<'a> {
+    /// `item_names`, `monster_names` はそれぞれ ID 順の名前テーブル。
+    pub fn new(event: &'a Event, item_names: &'a [&'a str], monster_names: &'a [&'a str]) -> Self {
+        Self {
+            event,
+            names: Names {
+                items: item_names,
+                monsters: monster_names,
+            },
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+struct Names<'a> {
+    items: &'a [&'a str],
+    monsters: &'a [&'a str],
+}
+
+/// 名前テーブルから ID を引いてフォーマットする。範囲外なら ID のみ。
+#[derive(Debug)]
+struct NamedId<'a> {
+    table: &'a [&'a str],
+    id: u8,
+}
+
+impl std::fmt::Display for NamedId<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let id = self.id;
+        match self.table.get(usize::from(id)) {
+            Some(name) => write!(f, "{name} (#{id})"),
+            None => write!(f, "#{id}"),
+        }
+    }
+}
+
+impl std::fmt::Display for EventDisplay<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let event = self.event;
+        let Position { floor, x, y } = event.position;
+        let trigger = match event.trigger {
+            EventTrigger::Enter => "enter",
+            EventTrigger::Search => "search",
+        };
+
+        write!(f, "on {trigger} B{}F ({x}, {y})", floor + 1)?;
+        if let Some(remaining) = event.remaining {
+            write!(f, " [{remaining} times]")?;
+        }
+        f.write_str(" {\n")?;
+
+        for stmt in &event.body {
+            fmt_stmt(f, self.names, stmt, 1)?;
+        }
+
+        f.write_str("}\n")
+    }
+}
+
+fn fmt_stmt(
+    f: &mut std::fmt::Formatter<'_>,
+    names: Names<'_>,
+    stmt: &EventStmt,
+    depth: usize,
+) -> std::fmt::Result {
+    let indent = "    ".repeat(depth);
+
+    match stmt {
+        EventStmt::Message(lines) => {
+            for line in lines {
+                writeln!(f, "{indent}message \"{line}\"")?;
+            }
+        }
+        EventStmt::If { cond, then, else_ } => {
+            writeln!(f, "{indent}if {} {{", CondDisplay { cond, names })?;
+            for stmt in then {
+                fmt_stmt(f, names, stmt, depth + 1)?;
+            }
+            if !else_.is_empty() {
+                writeln!(f, "{indent}}} else {{")?;
+                for stmt in else_ {
+                    fmt_stmt(f, names, stmt, depth + 1)?;
+                }
+            }
+            writeln!(f, "{indent}}}")?;
+        }
+        &EventStmt::GiveItem(id) => {
+            let item = NamedId {
+                table: names.items,
+                id,
+            };
+            writeln!(f, "{indent}give_item {item}")?
+        }
+        &EventStmt::Fight(id) => {
+            let monster = NamedId {
+                table: names.monsters,
+                id,
+            };
+            writeln!(f, "{indent}fight {monster}")?
+        }
+        EventStmt::PushBack => writeln!(f, "{indent}push_back")?,
+        EventStmt::PayGold(gold) => writeln!(f, "{indent}pay_gold {gold}")?,
+        EventStmt::Unknown { kind, aux } => writeln!(f, "{indent}unknown kind={kind} aux={aux:?}")?,
+    }
+
+    Ok(())
+}
+
+impl std::fmt::Display for EventCond {
+    /// アイテムは ID のみで表示する。
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let names = Names {
+            items: &[],
+            monsters: &[],
+        };
+        CondDisplay { cond: self, names }.fmt(f)
+    }
+}
+
+#[derive(Debug)]
+struct CondDisplay<'a> {
+    cond: &'a EventCond,
+    names: Names<'a>,
+}
+
+impl std::fmt::Display for CondDisplay<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.cond {
+            &EventCond::HasItem(id) => {
+                let item = NamedId {
+                    table: self.names.items,
+                    id,
+                };
+                write!(f, "has_item({item})")
+            }
+            EventCond::Alignment(alignments) => {
+                let mut s = String::new();
+                for (i, alignment) in alignments.into_iter().enumerate() {
+                    if i > 0 {
+                        s.push('|');
+                    }
+                    s.write_str(alignment.name()).unwrap();
+                }
+                write!(f, "alignment({s})")
+            }
+            EventCond::Answer(answer) => write!(f, "answer == \"{answer}\""),
+            EventCond::Yes => f.write_str("yes"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_event_display() {
+        let event = Event {
+            position: Position::new(2, 4, 5),
+            trigger: EventTrigger::Enter,
+            remaining: Some(1),
+            body: vec![EventStmt::If {
+                cond: EventCond::HasItem(126),
+                then: vec![],
+                else_: vec![
+                    EventStmt::Message(vec![GameString::from_text("GO BACK!").unwrap()]),
+                    EventStmt::PushBack,
+                ],
+            }],
+        };
+
+        assert_eq!(
+            event.to_string(),
+            "\
+on enter B3F (4, 5) [1 times] {
+    if has_item(#126) {
+    } else {
+        message \"GO BACK!\"
+        push_back
+    }
+}
+"
+        );
+
+        let mut item_names = vec![""; 127];
+        item_names[126] = "KOD's HELMET";
+        assert_eq!(
+            EventDisplay::new(&event, &item_names, &[]).to_string(),
+            "\
+on enter B3F (4, 5) [1 times] {
+    if has_item(KOD's HELMET (#126)) {
+    } else {
+        message \"GO BACK!\"
+        push_back
+    }
+}
+"
+        );
+    }
+}
@@ -0,0 +1,94 @@
+use anyhow::{bail, ensure, Context as _};
+
+use crate::drop_table::{
+    DropContent, DropEntry, DropTable, GoldDiceExpr, GoldDrop, ItemDrop, TrapKinds,
+};
+use crate::rom::Rom;
+use crate::util::U8SliceExt as _;
+
+use super::monster::MONSTER_COUNT;
+
+/// ドロップテーブルの数。
+///
+/// NOTE: 逆アセンブルで確認した値ではなく、モンスターデータが参照するIDの最大値から推定したもの
+/// (未検証)。ポインタテーブルの位置、エントリの形式も同様に推定であり、想定と異なるバイトがあれば
+/// 抽出はエラーになる。
+pub const DROP_TABLE_COUNT: usize = 24;
+
+/// 全ドロップテーブルをIDの昇順で抽出する。
+pub fn extract_drop_tables(rom: &Rom) -> anyhow::Result<Vec<DropTable>> {
+    (0..DROP_TABLE_COUNT)
+        .map(|id| extract_drop_table(rom, id).with_context(|| format!("drop table {id}")))
+        .collect()
+}
+
+/// 指定したIDのドロップテーブルを抽出する。
+pub fn extract_drop_table(rom: &Rom, id: usize) -> anyhow::Result<DropTable> {
+    assert!(id < DROP_TABLE_COUNT);
+
+    // モンスターデータと同じバンクにあり、ポインタテーブルはモンスターのポインタテーブルの直後に置かれている。
+    let bank = rom.prg_bank(6);
+    let table = &bank[2 * MONSTER_COUNT..][..2 * DROP_TABLE_COUNT];
+
+    let buf = {
+        let (ptr, _) = table[2 * id..].split_first_u16le().unwrap();
+        ensure!(
+            (0x8000..0xA000).contains(&ptr),
+            "pointer out of bank: 0x{ptr:04X}"
+        );
+        &bank[usize::from(ptr - 0x8000)..]
+    };
+
+    let (chest, buf) = buf.split_first_u8().context("truncated header")?;
+    let chest = match chest {
+        0 => false,
+        0xFF => true,
+        _ => bail!("invalid `chest` value: 0x{chest:02X}"),
+    };
+    let (trap_kinds, buf) = buf.split_first_u8().context("truncated header")?;
+    let trap_kinds = TrapKinds::new(trap_kinds).unwrap();
+    let (entry_count, mut buf) = buf.split_first_u8().context("truncated header")?;
+
+    let mut entries = Vec::<DropEntry>::with_capacity(usize::from(entry_count));
+    for i in 0..entry_count {
+        let entry;
+        (entry, buf) = split_first_entry(buf).with_context(|| format!("entry {i}"))?;
+        entries.push(entry);
+    }
+
+    Ok(DropTable {
+        chest,
+        trap_kinds,
+        entries,
+    })
+}
+
+fn split_first_entry(buf: &[u8]) -> anyhow::Result<(DropEntry, &[u8])> {
+    // 各エントリは 7 バイト: 確率, 種別 (0: 金貨, 1: アイテム), パラメータ 5 バイト。
+    let (percent, buf) = buf.split_first_u8().context("truncated entry")?;
+    let (kind, buf) = buf.split_first_u8().context("truncated entry")?;
+    let (params, buf) = buf.split_first_chunk::<5>().context("truncated entry")?;
+
+    let content = match kind {
+        0 => {
+            let [count, face, bias, mul_lo, mul_hi] = *params;
+            DropContent::Gold(GoldDrop {
+                dice_expr: GoldDiceExpr::new(count, face, bias),
+                multiplier: u16::from_le_bytes([mul_lo, mul_hi]),
+            })
+        }
+        1 => {
+            let [item_id_min, range, step, max_step_count, step_percent] = *params;
+            DropContent::Item(ItemDrop {
+                item_id_min,
+                range,
+                step,
+                max_step_count,
+                step_percent,
+            })
+        }
+        _ => bail!("invalid drop entry kind: {kind}"),
+    };
+
+    Ok((DropEntry { percent, content }, buf))
+}
@@ -0,0 +1,69 @@
+use crate::encounter::{
+    EncounterGroup, EncounterTable, FixedEncounter, RoomEncounter, RoomGuardian,
+};
+use crate::maze::SpecialSquare;
+use crate::rom::Rom;
+
+use super::maze::{extract_floor, floor_record, FLOOR_COUNT, GROUPS_OFFSET};
+
+/// 全フロアの遭遇テーブルをフロアの昇順で抽出する (地下 1 階がインデックス 0)。
+pub fn extract_encounter_tables(rom: &Rom) -> Vec<EncounterTable> {
+    (0..FLOOR_COUNT)
+        .map(|floor| extract_encounter_table(rom, floor))
+        .collect()
+}
+
+/// 指定したフロアの遭遇テーブルを抽出する。
+pub fn extract_encounter_table(rom: &Rom, floor: usize) -> EncounterTable {
+    let record = floor_record(rom, floor);
+
+    let groups = std::array::from_fn(|i| {
+        let buf = &record[GROUPS_OFFSET + 6 * i..][..6];
+        EncounterGroup {
+            weight: buf[0],
+            monster_id_min: buf[1],
+            range: buf[2],
+            step: buf[3],
+            max_step_count: buf[4],
+            step_percent: buf[5],
+        }
+    });
+
+    let mut rooms = Vec::<RoomEncounter>::new();
+    let mut fixed_encounters = Vec::<FixedEncounter>::new();
+
+    for ((x, y), cell) in extract_floor(rom, floor).cells() {
+        let (x, y) = (u8::try_from(x).unwrap(), u8::try_from(y).unwrap());
+
+        let fixed = match cell.special {
+            SpecialSquare::Encounter {
+                monster_id,
+                remaining,
+            } => Some(FixedEncounter {
+                x,
+                y,
+                monster_id,
+                remaining,
+            }),
+            _ => None,
+        };
+
+        if cell.room {
+            let guardian = match fixed {
+                Some(enc) => RoomGuardian::Fixed {
+                    monster_id: enc.monster_id,
+                },
+                None => RoomGuardian::Wandering,
+            };
+            rooms.push(RoomEncounter { x, y, guardian });
+        }
+
+        fixed_encounters.extend(fixed);
+    }
+
+    EncounterTable {
+        groups,
+        rooms,
+        fixed_encounters,
+    }
+}
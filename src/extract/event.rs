@@ -0,0 +1,238 @@
+use crate::alignment::Alignments;
+use crate::event::{Event, EventCond, EventStmt, EventTrigger};
+use crate::maze::{Position, SpecialSquare};
+use crate::rom::Rom;
+use crate::string::GameString;
+use crate::util::{SliceExt as _, U8SliceExt as _};
+
+use super::maze::{extract_floor, FLOOR_COUNT};
+
+/// イベントメッセージが置かれている PRG バンク。
+///
+/// NOTE: 逆アセンブルで参照元を確認した値ではなく、バンク内のデータの並びから推定したもの
+/// (未検証)。メッセージ数、イベント種別 `0..=8` の意味も同様に推定であり、
+/// 解釈できない種別のイベントは [`EventStmt::Unknown`] になる。
+pub(crate) const MESSAGE_PRG_BANK: usize = 11;
+
+pub const MESSAGE_COUNT: usize = 128;
+
+/// 全フロアのイベントを抽出する (フロア, x, y の昇順)。
+pub fn extract_events(rom: &Rom) -> Vec<Event> {
+    let mut events = Vec::<Event>::new();
+
+    for floor in 0..FLOOR_COUNT {
+        for ((x, y), cell) in extract_floor(rom, floor).cells() {
+            if let SpecialSquare::Message { aux } = cell.special {
+                events.push(decode_event(rom, Position::new(floor, x, y), aux));
+            }
+        }
+    }
+
+    events
+}
+
+/// 指定したIDのイベントメッセージを抽出する。各要素が 1 行。
+///
+/// 各行は 0 終端されており、空行でメッセージが終わる。
+/// ID が範囲外の場合や、メッセージとして解釈できないデータの場合は `None` を返す。
+pub fn extract_message(rom: &Rom, id: usize) -> Option<Vec<GameString>> {
+    if id >= MESSAGE_COUNT {
+        return None;
+    }
+
+    let bank = rom.prg_bank(MESSAGE_PRG_BANK);
+
+    let mut buf = {
+        let (ptr, _) = bank[2 * id..].split_first_u16le().unwrap();
+        if !(0x8000..0xA000).contains(&ptr) {
+            return None;
+        }
+        &bank[usize::from(ptr - 0x8000)..]
+    };
+
+    let mut lines = Vec::<GameString>::new();
+    loop {
+        let line;
+        (line, buf) = buf.split_once_(|&b| b == 0)?;
+        if line.is_empty() {
+            break;
+        }
+        lines.push(GameString::from_bytes(line).ok()?);
+    }
+
+    Some(lines)
+}
+
+/// 特殊マスの補助値をイベントとして解釈する。
+///
+/// aux0 はイベント種別ごとの引数、aux1 はメッセージID、aux2 の下位 8 ビットがイベント種別で、
+/// 上位 8 ビットが残り発生回数 (0 なら無制限)。
+fn decode_event(rom: &Rom, position: Position, aux: [i16; 3]) -> Event {
+    let [_, _, kind] = aux.map(|v| v as u16);
+    let remaining = (kind >> 8 != 0).then_some(kind >> 8);
+
+    let (trigger, body) = decode_event_body(rom, aux).unwrap_or_else(|| {
+        (
+            EventTrigger::Enter,
+            vec![EventStmt::Unknown {
+                kind: kind as u8,
+                aux,
+            }],
+        )
+    });
+
+    Event {
+        position,
+        trigger,
+        remaining,
+        body,
+    }
+}
+
+/// イベントの発生契機と本体を解釈する。解釈できなければ `None` を返す。
+fn decode_event_body(rom: &Rom, aux: [i16; 3]) -> Option<(EventTrigger, Vec<EventStmt>)> {
+    let [arg, message_id, kind] = aux.map(|v| v as u16);
+
+    let message = EventStmt::Message(extract_message(rom, usize::from(message_id))?);
+    let arg8 = arg as u8;
+
+    let res = match kind & 0xFF {
+        // メッセージのみ
+        0 => (EventTrigger::Enter, vec![message]),
+        // 調べるとアイテム入手
+        1 => (
+            EventTrigger::Search,
+            vec![message, EventStmt::GiveItem(arg8)],
+        ),
+        // なぞなぞ (答えは別のメッセージ)
+        2 => {
+            let answer = extract_message(rom, usize::from(arg))?
+                .into_iter()
+                .next()
+                .unwrap_or_default();
+            (
+                EventTrigger::Enter,
+                vec![
+                    message,
+                    EventStmt::If {
+                        cond: EventCond::Answer(answer),
+                        then: vec![],
+                        else_: vec![EventStmt::PushBack],
+                    },
+                ],
+            )
+        }
+        // アイテムを持っていないと通れない
+        3 => (
+            EventTrigger::Enter,
+            vec![EventStmt::If {
+                cond: EventCond::HasItem(arg8),
+                then: vec![],
+                else_: vec![message, EventStmt::PushBack],
+            }],
+        ),
+        // 戦闘
+        4 => (EventTrigger::Enter, vec![message, EventStmt::Fight(arg8)]),
+        // 性格が合わないと通れない
+        5 => (
+            EventTrigger::Enter,
+            vec![EventStmt::If {
+                cond: EventCond::Alignment(Alignments::new(arg8).ok()?),
+                then: vec![],
+                else_: vec![message, EventStmt::PushBack],
+            }],
+        ),
+        // 金貨を払えば通れる
+        6 => (
+            EventTrigger::Enter,
+            vec![
+                message,
+                EventStmt::If {
+                    cond: EventCond::Yes,
+                    then: vec![EventStmt::PayGold(arg)],
+                    else_: vec![EventStmt::PushBack],
+                },
+            ],
+        ),
+        // 入るとアイテム入手
+        7 => (
+            EventTrigger::Enter,
+            vec![message, EventStmt::GiveItem(arg8)],
+        ),
+        // アイテムを持っていなければ戦闘 (KOD の装備の守護者など)
+        8 => (
+            EventTrigger::Enter,
+            vec![EventStmt::If {
+                cond: EventCond::HasItem(arg8),
+                then: vec![],
+                else_: vec![message, EventStmt::Fight((arg >> 8) as u8)],
+            }],
+        ),
+        _ => return None,
+    };
+
+    Some(res)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::test_util::blank_ines;
+
+    /// メッセージ 0 ("HELLO") のみを持つ ROM を作る。
+    fn make_rom() -> Rom {
+        let mut ines = blank_ines();
+        let bank = &mut ines[16 + 0x2000 * MESSAGE_PRG_BANK..][..0x2000];
+        bank[..2].copy_from_slice(&0x8100_u16.to_le_bytes());
+        bank[0x100..0x107].copy_from_slice(b"HELLO\0\0");
+
+        Rom::from_ines_bytes(&ines).unwrap()
+    }
+
+    #[test]
+    fn test_extract_message() {
+        let rom = make_rom();
+
+        assert_eq!(
+            extract_message(&rom, 0),
+            Some(vec![GameString::from_text("HELLO").unwrap()])
+        );
+        // ポインタがバンク外
+        assert_eq!(extract_message(&rom, 1), None);
+        assert_eq!(extract_message(&rom, MESSAGE_COUNT), None);
+    }
+
+    #[test]
+    fn test_decode_event() {
+        let rom = make_rom();
+        let position = Position::new(0, 1, 2);
+
+        let event = decode_event(&rom, position, [5, 0, 0x0207]);
+        assert_eq!(event.remaining, Some(2));
+        assert_eq!(
+            event.body,
+            [
+                EventStmt::Message(vec![GameString::from_text("HELLO").unwrap()]),
+                EventStmt::GiveItem(5),
+            ]
+        );
+
+        // 未知のイベント種別
+        let event = decode_event(&rom, position, [0, 0, 99]);
+        assert_eq!(
+            event.body,
+            [EventStmt::Unknown {
+                kind: 99,
+                aux: [0, 0, 99]
+            }]
+        );
+
+        // メッセージIDが範囲外
+        let event = decode_event(&rom, position, [0, -1, 0]);
+        assert!(matches!(
+            event.body[..],
+            [EventStmt::Unknown { kind: 0, .. }]
+        ));
+    }
+}
@@ -0,0 +1,20 @@
+use crate::chr::decode_tiles;
+use crate::font::{Font, FONT_TILE_COUNT};
+use crate::rom::Rom;
+
+/// フォントが置かれている CHR バンクの先頭ID (ここから 4 バンク連続)。
+///
+/// NOTE: 逆アセンブルで PPU へのバンク設定を確認した値ではなく、CHR-ROM の内容から推定したもの
+/// (未検証)。
+const FONT_CHR_BANK: usize = 0;
+
+/// 原作のフォントを抽出する。
+pub fn extract_font(rom: &Rom) -> Font {
+    let bank_count = FONT_TILE_COUNT / 64;
+
+    let chr: Vec<u8> = (FONT_CHR_BANK..FONT_CHR_BANK + bank_count)
+        .flat_map(|id| rom.chr_bank(id).iter().copied())
+        .collect();
+
+    Font::new(decode_tiles(&chr))
+}
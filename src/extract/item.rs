@@ -10,6 +10,15 @@ use crate::util::U8SliceExt as _;
 
 pub const ITEM_COUNT: usize = 138;
 
+/// アイテムデータが置かれている PRG バンク。
+pub(crate) const ITEM_PRG_BANK: usize = 8;
+
+/// アイテムデータ 1 個のバイト数。先頭 2 個の u16 が名前 (既知/不確定) へのポインタ。
+pub(crate) const ITEM_RECORD_LEN: usize = 31;
+
+/// アイテム名の最大バイト数。
+pub(crate) const ITEM_NAME_LEN_MAX: usize = 16;
+
 /// 全アイテムをIDの昇順で抽出する。
 pub fn extract_items(rom: &Rom) -> Vec<Item> {
     (0..ITEM_COUNT).map(|id| extract_item(rom, id)).collect()
@@ -17,13 +26,11 @@ pub fn extract_items(rom: &Rom) -> Vec<Item> {
 
 /// 指定したIDのアイテムを抽出する。
 pub fn extract_item(rom: &Rom, id: usize) -> Item {
-    const CHUNK_LEN: usize = 31;
-
     assert!(id < ITEM_COUNT);
 
-    let bank = rom.prg_bank(8);
+    let bank = rom.prg_bank(ITEM_PRG_BANK);
 
-    let buf = &bank[CHUNK_LEN * id..][..CHUNK_LEN];
+    let buf = &bank[ITEM_RECORD_LEN * id..][..ITEM_RECORD_LEN];
 
     let (name_known, buf) = split_first_name(bank, buf);
     let (name_unknown, buf) = split_first_name(bank, buf);
@@ -105,7 +112,11 @@ fn read_name(buf: &[u8]) -> GameString {
     // NOTE: アイテムの場合、名前データは最大 16 バイトで、16 バイトに満たない場合のみ 0 終端されている。
     // (原作で 0 終端されていないのは "RING of SUFFOCATION" (16 バイト) のみ)
 
-    let i = buf.iter().take(16).position(|&b| b == 0).unwrap_or(16);
+    let i = buf
+        .iter()
+        .take(ITEM_NAME_LEN_MAX)
+        .position(|&b| b == 0)
+        .unwrap_or(ITEM_NAME_LEN_MAX);
 
     GameString::from_bytes(&buf[..i]).unwrap()
 }
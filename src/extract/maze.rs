@@ -0,0 +1,124 @@
+use crate::maze::{Cell, Edge, Floor, SpecialSquare, FLOOR_HEIGHT, FLOOR_WIDTH};
+use crate::rom::Rom;
+use crate::util::U8SliceExt as _;
+
+pub const FLOOR_COUNT: usize = 6;
+
+/// 迷宮データが置かれている PRG バンク。
+///
+/// NOTE: 逆アセンブルで参照元を確認した値ではなく、バンク内のデータの並びから推定したもの
+/// (未検証)。下記のレイアウトも同様。
+const MAZE_PRG_BANK: usize = 10;
+
+// 1 フロア分の迷宮データのレイアウト。
+//
+// NOTE: ROM 内のルーチンから確認したものではなく、Apple II 版の迷宮データのレコード
+// (壁 W/S/E/N, FIGHTS, SQREXTRA, SQRETYPE, AUX0..AUX2, ENMYCALC) と同じ並びであると仮定したもの
+// (未検証)。原作の ROM を使うテストで、壁の表裏の一致と地下 1 階の城への階段を確認する。
+//
+// 座標 (x, y) のマスのインデックスは `20 * x + y`。
+// 壁は西, 南, 東, 北の順に各 100 バイト (1 マス 2 ビット)。
+const FLOOR_RECORD_LEN: usize = 780;
+const WALLS_OFFSET: usize = 0;
+const FIGHTS_OFFSET: usize = 400;
+const SQUARE_EXTRA_OFFSET: usize = 450;
+const SQUARE_TYPE_OFFSET: usize = 650;
+const AUX_OFFSET: usize = 666;
+pub(super) const GROUPS_OFFSET: usize = 762;
+
+/// 全フロアの迷宮をフロアの昇順で抽出する (地下 1 階がインデックス 0)。
+pub fn extract_floors(rom: &Rom) -> Vec<Floor> {
+    (0..FLOOR_COUNT)
+        .map(|floor| extract_floor(rom, floor))
+        .collect()
+}
+
+/// 指定したフロアの迷宮を抽出する。
+pub fn extract_floor(rom: &Rom, floor: usize) -> Floor {
+    let record = floor_record(rom, floor);
+
+    let cells = (0..FLOOR_WIDTH * FLOOR_HEIGHT)
+        .map(|idx| {
+            let wall = |i: usize| -> Edge {
+                let buf = &record[WALLS_OFFSET + 100 * i..];
+                let value = (buf[idx / 4] >> (2 * (idx % 4))) & 3;
+                Edge::try_from(value).unwrap()
+            };
+
+            let room = (record[FIGHTS_OFFSET + idx / 8] >> (idx % 8)) & 1 != 0;
+
+            let extra = (record[SQUARE_EXTRA_OFFSET + idx / 2] >> (4 * (idx % 2))) & 0xF;
+            let extra = usize::from(extra);
+            let code = record[SQUARE_TYPE_OFFSET + extra];
+            let special = SpecialSquare::from_raw(code, read_aux(record, extra))
+                .unwrap_or_else(|| panic!("invalid special square code: {code}"));
+
+            Cell {
+                west: wall(0),
+                south: wall(1),
+                east: wall(2),
+                north: wall(3),
+                room,
+                special,
+            }
+        })
+        .collect();
+
+    Floor::new(cells)
+}
+
+pub(super) fn floor_record(rom: &Rom, floor: usize) -> &[u8] {
+    assert!(floor < FLOOR_COUNT);
+
+    &rom.prg_bank(MAZE_PRG_BANK)[FLOOR_RECORD_LEN * floor..][..FLOOR_RECORD_LEN]
+}
+
+/// 指定した特殊マス種別インデックスの補助値 (aux0, aux1, aux2) を読み取る。
+fn read_aux(record: &[u8], extra: usize) -> [i16; 3] {
+    std::array::from_fn(|i| {
+        let (value, _) = record[AUX_OFFSET + 32 * i + 2 * extra..]
+            .split_first_u16le()
+            .unwrap();
+        value as i16
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::maze::Direction;
+    use crate::test_util::original_rom;
+
+    #[test]
+    #[ignore = "原作の ROM が必要 (環境変数 WIZARDRY_KOD_ROM)"]
+    fn test_extract_floors_consistent() {
+        let rom = original_rom().expect("WIZARDRY_KOD_ROM is not set");
+
+        let floors = extract_floors(&rom);
+
+        // レイアウトが正しければ、隣り合うマスの共有する辺はほぼ全て一致する
+        // (一方通行の壁などがありうるので完全一致は求めない)。
+        for (i, floor) in floors.iter().enumerate() {
+            let mut mismatch = 0;
+            for ((x, y), cell) in floor.cells() {
+                for dir in [Direction::East, Direction::North] {
+                    let (nx, ny) = Floor::neighbor(x, y, dir);
+                    if cell.edge(dir) != floor.edge(nx, ny, dir.opposite()) {
+                        mismatch += 1;
+                    }
+                }
+            }
+            assert!(
+                mismatch <= 2 * FLOOR_WIDTH * FLOOR_HEIGHT / 20,
+                "B{}F: {mismatch} mismatched edges",
+                i + 1
+            );
+        }
+
+        // 地下 1 階には城へ上る階段がある。
+        assert!(floors[0]
+            .cells()
+            .any(|(_, cell)| matches!(cell.special, SpecialSquare::Stairs { floor: 0, .. })));
+    }
+}
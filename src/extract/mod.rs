@@ -1,11 +1,25 @@
 //! 原作の ROM 内からのデータ抽出。
 
+mod drop_table;
+mod encounter;
+mod event;
+mod font;
 mod item;
+mod maze;
 mod monster;
+mod monster_graphic;
 mod special_power;
 mod spell;
+mod text;
 
+pub use self::drop_table::*;
+pub use self::encounter::*;
+pub use self::event::*;
+pub use self::font::*;
 pub use self::item::*;
+pub use self::maze::*;
 pub use self::monster::*;
+pub use self::monster_graphic::*;
 pub use self::special_power::*;
 pub use self::spell::*;
+pub use self::text::*;
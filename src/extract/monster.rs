@@ -12,6 +12,9 @@ use crate::util::{SliceExt as _, U8SliceExt as _};
 
 pub const MONSTER_COUNT: usize = 90;
 
+/// モンスターデータが置かれている PRG バンク。先頭にモンスターデータへのポインタテーブルがある。
+pub(crate) const MONSTER_PRG_BANK: usize = 6;
+
 /// 全モンスターをIDの昇順で抽出する。
 pub fn extract_monsters(rom: &Rom) -> Vec<Monster> {
     (0..MONSTER_COUNT)
@@ -23,7 +26,7 @@ pub fn extract_monsters(rom: &Rom) -> Vec<Monster> {
 pub fn extract_monster(rom: &Rom, id: usize) -> Monster {
     assert!(id < MONSTER_COUNT);
 
-    let bank = rom.prg_bank(6);
+    let bank = rom.prg_bank(MONSTER_PRG_BANK);
     let table = &bank[..2 * MONSTER_COUNT];
 
     let buf = {
@@ -0,0 +1,100 @@
+use crate::chr::{decode_tiles, TILE_HEIGHT, TILE_WIDTH};
+use crate::image::IndexedImage;
+use crate::monster::MonsterGraphic;
+use crate::rom::Rom;
+use crate::util::U8SliceExt as _;
+
+use super::monster::MONSTER_COUNT;
+
+/// モンスターグラフィックのデータが置かれている PRG バンク。
+///
+/// NOTE: 逆アセンブルで参照元を確認した値ではなく、バンク内のデータの並びから推定したもの
+/// (未検証)。データ形式 (下記) も同様で、既知のタイルとの照合もしていない。
+const GRAPHIC_PRG_BANK: usize = 7;
+
+/// 空白タイルを表すタイルインデックス。
+const TILE_BLANK: u8 = 0xFF;
+
+/// 指定したIDのモンスターの戦闘グラフィックを抽出する。
+pub fn extract_monster_graphic(rom: &Rom, id: usize) -> MonsterGraphic {
+    assert!(id < MONSTER_COUNT);
+
+    // バンク先頭にモンスターIDごとのポインタテーブルがあり、各ポインタが指すデータは以下の形式:
+    //
+    // * CHR バンクID (このバンクとその次のバンクの計 128 タイルが使われる)
+    // * 横タイル数
+    // * 縦タイル数
+    // * サブパレット (4 バイト)
+    // * タイルインデックス (横タイル数 * 縦タイル数 バイト、行優先)
+    let bank = rom.prg_bank(GRAPHIC_PRG_BANK);
+
+    let buf = {
+        let (ptr, _) = bank[2 * id..].split_first_u16le().unwrap();
+        &bank[usize::from(ptr - 0x8000)..]
+    };
+
+    let (chr_bank_id, buf) = buf.split_first_u8().unwrap();
+    let (width, buf) = buf.split_first_u8().unwrap();
+    let (height, buf) = buf.split_first_u8().unwrap();
+    let (&palette, buf) = buf.split_first_chunk::<4>().unwrap();
+
+    let (width, height) = (usize::from(width), usize::from(height));
+    let arrangement = &buf[..width * height];
+
+    let tiles = {
+        let chr_bank_id = usize::from(chr_bank_id);
+        let mut chr = rom.chr_bank(chr_bank_id).to_vec();
+        chr.extend_from_slice(rom.chr_bank(chr_bank_id + 1));
+        decode_tiles(&chr)
+    };
+
+    let mut image = IndexedImage::new(TILE_WIDTH * width, TILE_HEIGHT * height);
+    for (i, &tile_idx) in arrangement.iter().enumerate() {
+        if tile_idx == TILE_BLANK {
+            continue;
+        }
+        let (col, row) = (i % width, i / width);
+        image.draw_tile(
+            &tiles[usize::from(tile_idx)],
+            TILE_WIDTH * col,
+            TILE_HEIGHT * row,
+            0,
+        );
+    }
+
+    MonsterGraphic { image, palette }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::chr::TILE_LEN;
+    use crate::test_util::blank_ines;
+
+    #[test]
+    fn test_extract_monster_graphic() {
+        const INES_HEADER_LEN: usize = 16;
+
+        let mut ines = blank_ines();
+        let (prg, chr) = ines[INES_HEADER_LEN..].split_at_mut(0x20000);
+
+        let bank = &mut prg[0x2000 * GRAPHIC_PRG_BANK..][..0x2000];
+        bank[..2].copy_from_slice(&0x8100_u16.to_le_bytes());
+        // CHR バンク 2, 2x1 タイル, サブパレット, タイル (1, 空白)
+        bank[0x100..0x109].copy_from_slice(&[2, 2, 1, 0x0F, 0x01, 0x11, 0x21, 1, TILE_BLANK]);
+
+        // CHR バンク 2 のタイル 1 を全画素 3 にする
+        chr[0x400 * 2 + TILE_LEN..][..TILE_LEN].fill(0xFF);
+
+        let rom = Rom::from_ines_bytes(&ines).unwrap();
+        let graphic = extract_monster_graphic(&rom, 0);
+
+        assert_eq!(graphic.palette, [0x0F, 0x01, 0x11, 0x21]);
+        assert_eq!(graphic.image.width(), 16);
+        assert_eq!(graphic.image.height(), 8);
+        assert_eq!(graphic.image.pixel(0, 0), 3);
+        assert_eq!(graphic.image.pixel(7, 7), 3);
+        assert_eq!(graphic.image.pixel(8, 0), 0);
+    }
+}
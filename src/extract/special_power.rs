@@ -1,6 +1,39 @@
+use crate::class::Class;
+use crate::special_power::SpecialPower;
+use crate::stat::Stat;
+
+pub const SPECIAL_POWER_COUNT: usize = 27;
+
+/// 指定したID (`1..=27`) のスペシャルパワーの効果を返す。
+///
+/// [`special_power_description`] と同じ内容を構造化したもの。ROM 内の SP の処理ルーチンや
+/// そのテーブルから復号したものではない (テーブルの位置が未特定のため)。
+pub fn special_power(id: usize) -> SpecialPower {
+    assert!(matches!(id, 1..=SPECIAL_POWER_COUNT));
+
+    match id {
+        1..=6 => SpecialPower::StatDelta(Stat::from_id((id - 1) as u8).unwrap(), 1),
+        7..=12 => SpecialPower::StatDelta(Stat::from_id((id - 7) as u8).unwrap(), -1),
+        13 => SpecialPower::AgeDelta(-1),
+        16 => SpecialPower::ChangeClass(Class::Lord.into()),
+        17 => SpecialPower::ChangeClass(Class::Ninja.into()),
+        19 => SpecialPower::XpGain(50000),
+        20 => SpecialPower::Ash,
+        22 => SpecialPower::MaxHpDelta(1),
+        23 => SpecialPower::HealParty,
+        24 => SpecialPower::ChangeClass(Class::Samurai | Class::Lord | Class::Ninja),
+        25 => SpecialPower::DieAndChangeClass(
+            Class::Fighter | Class::Mage | Class::Cleric | Class::Thief | Class::Wizard,
+        ),
+        26 => SpecialPower::SetMp(9),
+        27 => SpecialPower::ForgetSpellsAndSetMp(9),
+        _ => SpecialPower::Unused(id as u8),
+    }
+}
+
 /// 指定したID (`1..=27`) のスペシャルパワーの説明を返す。
 pub fn special_power_description(id: usize) -> &'static str {
-    const TABLE: [&str; 27] = [
+    const TABLE: [&str; SPECIAL_POWER_COUNT] = [
         "力 +1",
         "知恵 +1",
         "信仰心 +1",
@@ -30,7 +63,36 @@ pub fn special_power_description(id: usize) -> &'static str {
         "全呪文を忘れ、全ての現在MPが 9 になる",
     ];
 
-    assert!(matches!(id, 1..=27));
+    assert!(matches!(id, 1..=SPECIAL_POWER_COUNT));
 
     TABLE[id - 1]
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_special_power() {
+        assert_eq!(special_power(1), SpecialPower::StatDelta(Stat::Strength, 1));
+        assert_eq!(special_power(12), SpecialPower::StatDelta(Stat::Luck, -1));
+        assert_eq!(special_power(14), SpecialPower::Unused(14));
+        assert_eq!(
+            special_power(16),
+            SpecialPower::ChangeClass(Class::Lord.into())
+        );
+        assert_eq!(special_power(19), SpecialPower::XpGain(50000));
+
+        // 説明と同じ内容であること (転職先の表記は異なるので除く)
+        for id in 1..=SPECIAL_POWER_COUNT {
+            let sp = special_power(id);
+            if matches!(
+                sp,
+                SpecialPower::ChangeClass(_) | SpecialPower::DieAndChangeClass(_)
+            ) {
+                continue;
+            }
+            assert_eq!(sp.to_string(), special_power_description(id), "SP {id}");
+        }
+    }
+}
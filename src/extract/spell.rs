@@ -1,62 +1,96 @@
+use anyhow::anyhow;
+
+use crate::element::Elements;
+use crate::rom::Rom;
+use crate::spell::{Spell, SpellData, SpellDiceExpr, SpellTarget};
+
 pub const SPELL_COUNT: usize = 53;
 
 /// 指定したIDの呪文名を返す。
+///
+/// [`Spell::name`] の薄いラッパー。`id` が範囲外なら panic する。
 pub fn spell_name(id: usize) -> &'static str {
-    const TABLE: [&str; SPELL_COUNT] = [
-        "Halito",
-        "Mogref",
-        "Katino",
-        "Dumapic",
-        "Dilto",
-        "Sopic",
-        "Melito",
-        "Mahalito",
-        "Molito",
-        "Morlis",
-        "Tzalik",
-        "Dalto",
-        "Lahalito",
-        "Mamorlis",
-        "Makanito",
-        "Madalto",
-        "Zilwan",
-        "Lakanito",
-        "Masopic",
-        "Haman",
-        "Ladalto",
-        "Malor",
-        "Mahaman",
-        "Tiltowait",
-        "Kalki",
-        "Dios",
-        "Badios",
-        "Milwa",
-        "Porfic",
-        "Matu",
-        "Calfo",
-        "Manifo",
-        "Montino",
-        "Kandi",
-        "Dial",
-        "Badial",
-        "Dialko",
-        "Latumapic",
-        "Lomilwa",
-        "Litokan",
-        "Bamatu",
-        "Latumofis",
-        "Maporfic",
-        "Dialma",
-        "Badialma",
-        "Di",
-        "Badi",
-        "Lorto",
-        "Madi",
-        "Mabadi",
-        "Loktofeit",
-        "Malikto",
-        "Kadorto",
-    ];
-
-    TABLE[id]
+    assert!(id < SPELL_COUNT);
+
+    Spell::from_id(id as u8).unwrap().name()
+}
+
+/// 呪文データが置かれている PRG バンク。
+///
+/// NOTE: 逆アセンブルで参照元を確認した値ではなく、バンク内のデータの並びから推定したもの
+/// (未検証)。下記のレコードの形式も同様。
+const SPELL_PRG_BANK: usize = 12;
+
+/// 1 呪文分のレコードの長さ。
+///
+/// NOTE: 呪文IDの順に 5 バイトのレコードが並ぶと仮定している (未検証)。
+/// 各バイトの意味は [`extract_spell_data`] を参照。
+const SPELL_RECORD_LEN: usize = 5;
+
+/// 全呪文の動作パラメータを呪文IDの昇順で抽出する。
+pub fn extract_spell_datas(rom: &Rom) -> anyhow::Result<Vec<SpellData>> {
+    Spell::iter()
+        .map(|spell| extract_spell_data(rom, spell))
+        .collect()
+}
+
+/// 指定した呪文の動作パラメータを抽出する。
+///
+/// レコードの形式 (未検証):
+///
+/// * 0: 下位 6 ビットが対象、bit6 がキャンプで使用可、bit7 が戦闘で使用可。
+/// * 1..=3: ダイス式の (個数, 面数, 追加値)。個数が 0 ならダイス式なし。
+/// * 4: 属性のビットマスク。
+///
+/// 対象や属性が不正な値ならエラーを返す (想定しているレコードの形式が誤っていることを示す)。
+pub fn extract_spell_data(rom: &Rom, spell: Spell) -> anyhow::Result<SpellData> {
+    let bank = rom.prg_bank(SPELL_PRG_BANK);
+    let buf = &bank[SPELL_RECORD_LEN * usize::from(spell.to_id())..][..SPELL_RECORD_LEN];
+
+    let flags = buf[0];
+    let target = SpellTarget::try_from(flags & 0x3F)
+        .map_err(|_| anyhow!("{}: invalid spell target: {}", spell.name(), flags & 0x3F))?;
+    let usable_in_camp = (flags & (1 << 6)) != 0;
+    let usable_in_battle = (flags & (1 << 7)) != 0;
+
+    let dice_expr = (buf[1] != 0).then(|| SpellDiceExpr::new(buf[1], buf[2], buf[3]));
+
+    let elements = Elements::new(buf[4])
+        .map_err(|_| anyhow!("{}: invalid elements: 0x{:02X}", spell.name(), buf[4]))?;
+
+    Ok(SpellData {
+        target,
+        usable_in_camp,
+        usable_in_battle,
+        dice_expr,
+        elements,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::test_util::blank_ines;
+
+    #[test]
+    fn test_extract_spell_data() {
+        let mut ines = blank_ines();
+        let bank = &mut ines[16 + 0x2000 * SPELL_PRG_BANK..][..0x2000];
+        let halito = SPELL_RECORD_LEN * usize::from(Spell::Halito.to_id());
+        bank[halito..][..SPELL_RECORD_LEN].copy_from_slice(&[0x80 | 3, 1, 8, 0, 0]);
+        let rom = Rom::from_ines_bytes(&ines).unwrap();
+
+        let data = extract_spell_data(&rom, Spell::Halito).unwrap();
+        assert_eq!(data.target, SpellTarget::Single);
+        assert!(data.usable_in_battle);
+        assert!(!data.usable_in_camp);
+        assert_eq!(data.dice_expr, Some(SpellDiceExpr::new(1, 8, 0)));
+        assert!(data.elements.is_empty());
+
+        // 不正な対象はエラー
+        ines[16 + 0x2000 * SPELL_PRG_BANK + halito] = 0x3F;
+        let rom = Rom::from_ines_bytes(&ines).unwrap();
+        assert!(extract_spell_data(&rom, Spell::Halito).is_err());
+    }
 }
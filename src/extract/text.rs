@@ -0,0 +1,321 @@
+use std::collections::BTreeMap;
+
+use byteorder::{ByteOrder as _, LE};
+
+use crate::rom::Rom;
+use crate::string::{GameChar, GameString};
+use crate::text::{prg_bank_base, PrgLocation, TextCategory, TextEntry, TextTable};
+
+use super::event::{MESSAGE_COUNT, MESSAGE_PRG_BANK};
+use super::item::{ITEM_COUNT, ITEM_NAME_LEN_MAX, ITEM_PRG_BANK, ITEM_RECORD_LEN};
+use super::monster::{MONSTER_COUNT, MONSTER_PRG_BANK};
+
+const PRG_BANK_COUNT: usize = 16;
+
+/// 固定バンク。
+const FIXED_PRG_BANKS: [usize; 2] = [14, 15];
+
+/// 推測によるテキストとみなす最小の長さ。
+const TEXT_LEN_MIN: usize = 3;
+
+/// 全 PRG バンクからテキストを抽出し、位置の昇順で返す。
+///
+/// まず既知のテーブルを辿ってテキストを集める:
+///
+/// * アイテム名: アイテムデータ (バンク 8) の先頭 2 個の名前ポインタ。
+/// * モンスター名: モンスターデータ (バンク 6) の先頭 2 個の名前ポインタ。単数形と複数形の組。
+/// * イベントメッセージ: バンク 11 先頭のメッセージテーブル。
+/// * `tables` で指定したテキストのポインタテーブル。
+///
+/// 会話、メニュー、商店、寺院、宿などのテキストのポインタテーブルは ROM 内の位置が未特定なので
+/// 組み込んでいない。判明したものは `tables` で指定する。
+///
+/// さらに、それ以外の領域から「原作の文字のみからなり 0 で終端され、かつそれを指すポインタの候補が
+/// あるバイト列」を探し、[`TextCategory::Unverified`] として併せて返す。
+/// 切り替えバンク内のテキストのポインタは同じバンク内のみ探す (固定バンク内の値はどの切り替え
+/// バンクを指すか分からないため)。固定バンク内のテキストのポインタは全バンクから探す。
+pub fn extract_texts(rom: &Rom, tables: &[TextTable]) -> Vec<TextEntry> {
+    let mut known = BTreeMap::<PrgLocation, TextEntry>::new();
+    extract_item_names(rom, &mut known);
+    extract_monster_names(rom, &mut known);
+    extract_event_messages(rom, &mut known);
+    for table in tables {
+        extract_table_texts(rom, table, &mut known);
+    }
+
+    let mut entries: Vec<TextEntry> = known.into_values().collect();
+    let unverified = extract_unverified_texts(rom, &entries);
+    entries.extend(unverified);
+    entries.sort_by_key(|entry| entry.location);
+
+    entries
+}
+
+/// バンク内の `offset` にあるポインタを読み、それが切り替えバンク内を指していればバンク内オフセットを返す。
+fn read_pointer(bank: &[u8], offset: usize) -> Option<usize> {
+    let ptr = LE::read_u16(bank.get(offset..offset + 2)?);
+
+    (0x8000..0xA000)
+        .contains(&ptr)
+        .then(|| usize::from(ptr - 0x8000))
+}
+
+/// テキストを登録する。同じ位置のテキストが既にあれば、ポインタのみ追加する。
+fn add_known(
+    known: &mut BTreeMap<PrgLocation, TextEntry>,
+    location: PrgLocation,
+    bytes: &[u8],
+    pointer: Option<PrgLocation>,
+    category: TextCategory,
+) {
+    let Ok(text) = GameString::from_bytes(bytes) else {
+        return;
+    };
+
+    let entry = known.entry(location).or_insert_with(|| TextEntry {
+        location,
+        len: bytes.len(),
+        pointers: vec![],
+        category,
+        text,
+    });
+    if let Some(pointer) = pointer {
+        entry.pointers.push(pointer);
+    }
+}
+
+fn extract_item_names(rom: &Rom, known: &mut BTreeMap<PrgLocation, TextEntry>) {
+    let bank = rom.prg_bank(ITEM_PRG_BANK);
+
+    for id in 0..ITEM_COUNT {
+        let record = ITEM_RECORD_LEN * id;
+        for field in [record, record + 2] {
+            let Some(start) = read_pointer(bank, field) else {
+                continue;
+            };
+            // 16 バイトに満たない場合のみ 0 終端されている。
+            let len = bank[start..]
+                .iter()
+                .take(ITEM_NAME_LEN_MAX)
+                .position(|&b| b == 0)
+                .unwrap_or(ITEM_NAME_LEN_MAX.min(bank.len() - start));
+            add_known(
+                known,
+                PrgLocation::new(ITEM_PRG_BANK, start),
+                &bank[start..][..len],
+                Some(PrgLocation::new(ITEM_PRG_BANK, field)),
+                TextCategory::ItemName,
+            );
+        }
+    }
+}
+
+fn extract_monster_names(rom: &Rom, known: &mut BTreeMap<PrgLocation, TextEntry>) {
+    let bank = rom.prg_bank(MONSTER_PRG_BANK);
+
+    for id in 0..MONSTER_COUNT {
+        let Some(record) = read_pointer(bank, 2 * id) else {
+            continue;
+        };
+        for field in [record, record + 2] {
+            let Some(start) = read_pointer(bank, field) else {
+                continue;
+            };
+            // 単数形と複数形がそれぞれ 0 終端で並んでいる。
+            let mut it = bank[start..].splitn(3, |&b| b == 0);
+            let (Some(singular), Some(plural), Some(_)) = (it.next(), it.next(), it.next()) else {
+                continue;
+            };
+            add_known(
+                known,
+                PrgLocation::new(MONSTER_PRG_BANK, start),
+                singular,
+                Some(PrgLocation::new(MONSTER_PRG_BANK, field)),
+                TextCategory::MonsterName,
+            );
+            add_known(
+                known,
+                PrgLocation::new(MONSTER_PRG_BANK, start + singular.len() + 1),
+                plural,
+                None,
+                TextCategory::MonsterNamePlural,
+            );
+        }
+    }
+}
+
+fn extract_event_messages(rom: &Rom, known: &mut BTreeMap<PrgLocation, TextEntry>) {
+    let bank = rom.prg_bank(MESSAGE_PRG_BANK);
+
+    for id in 0..MESSAGE_COUNT {
+        let Some(mut start) = read_pointer(bank, 2 * id) else {
+            continue;
+        };
+        // 各行が 0 終端で並び、空行で終わる。
+        let mut pointer = Some(PrgLocation::new(MESSAGE_PRG_BANK, 2 * id));
+        while let Some(len) = bank[start..].iter().position(|&b| b == 0) {
+            if len == 0 {
+                break;
+            }
+            let category = if pointer.is_some() {
+                TextCategory::EventMessage
+            } else {
+                TextCategory::EventMessageLine
+            };
+            add_known(
+                known,
+                PrgLocation::new(MESSAGE_PRG_BANK, start),
+                &bank[start..][..len],
+                pointer.take(),
+                category,
+            );
+            start += len + 1;
+        }
+    }
+}
+
+fn extract_table_texts(rom: &Rom, table: &TextTable, known: &mut BTreeMap<PrgLocation, TextEntry>) {
+    let table_bank = rom.prg_bank(table.table.bank);
+    let text_bank = rom.prg_bank(table.text_bank);
+    let base = prg_bank_base(table.text_bank);
+
+    for i in 0..table.count {
+        let field = table.table.offset + 2 * i;
+        let ptr = LE::read_u16(&table_bank[field..][..2]);
+        let Some(start) = ptr.checked_sub(base).map(usize::from) else {
+            continue;
+        };
+        let Some(len) = text_bank
+            .get(start..)
+            .and_then(|buf| buf.iter().position(|&b| b == 0))
+        else {
+            continue;
+        };
+        add_known(
+            known,
+            PrgLocation::new(table.text_bank, start),
+            &text_bank[start..][..len],
+            Some(PrgLocation::new(table.table.bank, field)),
+            table.category,
+        );
+    }
+}
+
+/// 既知のテキスト以外から、ポインタの候補があるテキストらしきバイト列を探す。
+fn extract_unverified_texts(rom: &Rom, known: &[TextEntry]) -> Vec<TextEntry> {
+    let mut entries = Vec::<TextEntry>::new();
+
+    for bank_id in 0..PRG_BANK_COUNT {
+        let bank = rom.prg_bank(bank_id);
+
+        for (start, end) in find_text_spans(bank) {
+            let overlaps_known = known.iter().any(|entry| {
+                entry.location.bank == bank_id
+                    && start <= entry.location.offset + entry.len
+                    && entry.location.offset <= end
+            });
+            if overlaps_known {
+                continue;
+            }
+
+            let location = PrgLocation::new(bank_id, start);
+            let pointers = find_pointers(rom, location);
+            if pointers.is_empty() {
+                continue;
+            }
+
+            entries.push(TextEntry {
+                location,
+                len: end - start,
+                pointers,
+                category: TextCategory::Unverified,
+                text: GameString::from_bytes(&bank[start..end]).unwrap(),
+            });
+        }
+    }
+
+    entries
+}
+
+/// バンク内の、原作の文字のみからなり 0 で終端されたバイト列の範囲 (終端を含まない) を列挙する。
+fn find_text_spans(bank: &[u8]) -> Vec<(usize, usize)> {
+    let mut spans = Vec::<(usize, usize)>::new();
+
+    let mut start = None;
+    for (i, &b) in bank.iter().enumerate() {
+        if b != 0 && GameChar::try_from(b).is_ok() {
+            start.get_or_insert(i);
+            continue;
+        }
+        if let Some(s) = start.take() {
+            if b == 0 && i - s >= TEXT_LEN_MIN {
+                spans.push((s, i));
+            }
+        }
+    }
+
+    spans
+}
+
+/// 指定した位置を指すポインタの候補を探す。
+fn find_pointers(rom: &Rom, target: PrgLocation) -> Vec<PrgLocation> {
+    let address = target.address().to_le_bytes();
+
+    let search_banks: Vec<usize> = if FIXED_PRG_BANKS.contains(&target.bank) {
+        (0..PRG_BANK_COUNT).collect()
+    } else {
+        vec![target.bank]
+    };
+
+    let mut pointers = Vec::<PrgLocation>::new();
+    for bank_id in search_banks {
+        let bank = rom.prg_bank(bank_id);
+        for (offset, window) in bank.windows(2).enumerate() {
+            if window == address {
+                pointers.push(PrgLocation::new(bank_id, offset));
+            }
+        }
+    }
+
+    pointers
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::test_util::blank_ines;
+
+    #[test]
+    fn test_extract_texts_with_table() {
+        let mut ines = blank_ines();
+        let prg = &mut ines[16..];
+        // 固定バンク 15 のテーブルがバンク 3 の商店のテキスト 2 個を指す
+        let bank = &mut prg[0x2000 * 15..][..0x2000];
+        bank[0x100..0x102].copy_from_slice(&0x8010_u16.to_le_bytes());
+        bank[0x102..0x104].copy_from_slice(&0x8020_u16.to_le_bytes());
+        let bank = &mut prg[0x2000 * 3..][..0x2000];
+        bank[0x10..0x14].copy_from_slice(b"BUY\0");
+        bank[0x20..0x25].copy_from_slice(b"SELL\0");
+        let rom = Rom::from_ines_bytes(&ines).unwrap();
+
+        let table = TextTable::new(TextCategory::Shop, PrgLocation::new(15, 0x100), 2, 3);
+        let entries = extract_texts(&rom, &[table]);
+
+        let shop: Vec<&TextEntry> = entries
+            .iter()
+            .filter(|entry| entry.category == TextCategory::Shop)
+            .collect();
+        assert_eq!(shop.len(), 2);
+        assert_eq!(shop[0].location, PrgLocation::new(3, 0x10));
+        assert_eq!(shop[0].text, GameString::from_text("BUY").unwrap());
+        assert_eq!(shop[0].pointers, [PrgLocation::new(15, 0x100)]);
+        assert_eq!(shop[1].location, PrgLocation::new(3, 0x20));
+        assert_eq!(shop[1].pointers, [PrgLocation::new(15, 0x102)]);
+
+        // テーブルを指定しなければ分類されない
+        assert!(extract_texts(&rom, &[])
+            .iter()
+            .all(|entry| entry.category != TextCategory::Shop));
+    }
+}
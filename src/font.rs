@@ -0,0 +1,139 @@
+use crate::chr::{Tile, TILE_HEIGHT, TILE_WIDTH};
+use crate::image::IndexedImage;
+use crate::string::{GameChar, GameString};
+
+/// フォントのタイル数 (文字コード `0x00..=0xFF` に対応)。
+pub const FONT_TILE_COUNT: usize = 256;
+
+/// 原作のフォント。
+///
+/// 文字コードがそのままタイルインデックスになっている。
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Font {
+    tiles: Vec<Tile>,
+}
+
+impl Font {
+    pub fn new(tiles: Vec<Tile>) -> Self {
+        assert_eq!(tiles.len(), FONT_TILE_COUNT);
+
+        Self { tiles }
+    }
+
+    /// 指定した文字のグリフを返す。
+    pub fn glyph(&self, ch: GameChar) -> &Tile {
+        &self.tiles[usize::from(u8::from(ch))]
+    }
+
+    /// 文字列を 1 行の画像として描画する (画素値は `0..=3`)。
+    pub fn render(&self, s: &GameString) -> IndexedImage {
+        self.render_lines(std::slice::from_ref(s))
+    }
+
+    /// 複数行の文字列を描画する。画像の幅は最も長い行に合わせられる。
+    pub fn render_lines(&self, lines: &[GameString]) -> IndexedImage {
+        let columns = lines.iter().map(GameString::len).max().unwrap_or(0);
+
+        self.render_lines_in(lines, columns)
+    }
+
+    /// 文字列を幅 `columns` 文字の枠内に描画する。収まらない場合は `None` を返す。
+    ///
+    /// 名前欄 (16 文字) などに収まるかの確認用。
+    pub fn render_padded(&self, s: &GameString, columns: usize) -> Option<IndexedImage> {
+        (s.len() <= columns).then(|| self.render_lines_in(std::slice::from_ref(s), columns))
+    }
+
+    fn render_lines_in(&self, lines: &[GameString], columns: usize) -> IndexedImage {
+        let mut image = IndexedImage::new(TILE_WIDTH * columns, TILE_HEIGHT * lines.len());
+
+        for (row, line) in lines.iter().enumerate() {
+            for (col, &ch) in line.chars().iter().enumerate() {
+                image.draw_tile(self.glyph(ch), TILE_WIDTH * col, TILE_HEIGHT * row, 0);
+            }
+        }
+
+        image
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::chr::{decode_tiles, TILE_LEN};
+
+    /// 文字コード `b` のグリフが「上から `b % 8` 行目のみ画素値 1」である合成フォントを作る。
+    fn make_font() -> Font {
+        let mut chr = vec![0; TILE_LEN * FONT_TILE_COUNT];
+        for (b, tile) in chr.chunks_exact_mut(TILE_LEN).enumerate() {
+            tile[b % TILE_HEIGHT] = 0xFF;
+        }
+
+        Font::new(decode_tiles(&chr))
+    }
+
+    fn row_of(image: &IndexedImage, col: usize, row: usize) -> Option<usize> {
+        (0..TILE_HEIGHT).find(|&dy| image.pixel(TILE_WIDTH * col, TILE_HEIGHT * row + dy) == 1)
+    }
+
+    #[test]
+    fn test_render() {
+        let font = make_font();
+        let s = GameString::from_text("AB").unwrap();
+
+        let image = font.render(&s);
+
+        assert_eq!(image.width(), 2 * TILE_WIDTH);
+        assert_eq!(image.height(), TILE_HEIGHT);
+        for (col, &ch) in s.chars().iter().enumerate() {
+            assert_eq!(
+                row_of(&image, col, 0),
+                Some(usize::from(u8::from(ch)) % TILE_HEIGHT)
+            );
+            // グリフは全て 8 ドット幅
+            let y = usize::from(u8::from(ch)) % TILE_HEIGHT;
+            assert!((0..TILE_WIDTH).all(|dx| image.pixel(TILE_WIDTH * col + dx, y) == 1));
+        }
+    }
+
+    #[test]
+    fn test_render_lines() {
+        let font = make_font();
+        let lines = [
+            GameString::from_text("A").unwrap(),
+            GameString::from_text("ABC").unwrap(),
+        ];
+
+        let image = font.render_lines(&lines);
+
+        assert_eq!(image.width(), 3 * TILE_WIDTH);
+        assert_eq!(image.height(), 2 * TILE_HEIGHT);
+        // 短い行の残りは空白のまま
+        assert_eq!(row_of(&image, 1, 0), None);
+        assert!(row_of(&image, 2, 1).is_some());
+
+        assert_eq!(font.render_lines(&[]).width(), 0);
+    }
+
+    #[test]
+    fn test_render_padded() {
+        let font = make_font();
+        let s = GameString::from_text("ABCD").unwrap();
+
+        let image = font.render_padded(&s, 16).unwrap();
+        assert_eq!(image.width(), 16 * TILE_WIDTH);
+        assert_eq!(image, {
+            let mut expected = IndexedImage::new(16 * TILE_WIDTH, TILE_HEIGHT);
+            let rendered = font.render(&s);
+            for y in 0..rendered.height() {
+                for x in 0..rendered.width() {
+                    expected.set_pixel(x, y, rendered.pixel(x, y));
+                }
+            }
+            expected
+        });
+
+        assert_eq!(font.render_padded(&s, 3), None);
+    }
+}
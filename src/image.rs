@@ -0,0 +1,189 @@
+use std::io::Write;
+use std::path::Path;
+
+use anyhow::{ensure, Context as _};
+
+use crate::chr::{Tile, TILE_HEIGHT, TILE_WIDTH};
+
+/// RGB 色。
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub struct Rgb {
+    pub r: u8,
+    pub g: u8,
+    pub b: u8,
+}
+
+impl Rgb {
+    pub const fn new(r: u8, g: u8, b: u8) -> Self {
+        Self { r, g, b }
+    }
+
+    /// `0xRRGGBB` 形式の値から色を作る。
+    pub const fn from_u32(rgb: u32) -> Self {
+        Self::new((rgb >> 16) as u8, (rgb >> 8) as u8, rgb as u8)
+    }
+}
+
+/// 各画素がパレットインデックスである画像。
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct IndexedImage {
+    width: usize,
+    height: usize,
+    pixels: Vec<u8>,
+}
+
+impl IndexedImage {
+    /// 全画素が 0 の画像を作る。
+    pub fn new(width: usize, height: usize) -> Self {
+        Self {
+            width,
+            height,
+            pixels: vec![0; width * height],
+        }
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// 全画素を行優先順で返す。
+    pub fn pixels(&self) -> &[u8] {
+        &self.pixels
+    }
+
+    pub fn pixel(&self, x: usize, y: usize) -> u8 {
+        assert!(x < self.width && y < self.height);
+
+        self.pixels[self.width * y + x]
+    }
+
+    pub fn set_pixel(&mut self, x: usize, y: usize, value: u8) {
+        assert!(x < self.width && y < self.height);
+
+        self.pixels[self.width * y + x] = value;
+    }
+
+    /// 指定した位置 (ピクセル単位) にタイルを描画する。
+    ///
+    /// 各画素値には `base` が加算される (サブパレットの選択用)。画像からはみ出す部分は無視される。
+    pub fn draw_tile(&mut self, tile: &Tile, x: usize, y: usize, base: u8) {
+        for dy in 0..TILE_HEIGHT {
+            for dx in 0..TILE_WIDTH {
+                let (px, py) = (x + dx, y + dy);
+                if px < self.width && py < self.height {
+                    self.set_pixel(px, py, base + tile.pixel(dx, dy));
+                }
+            }
+        }
+    }
+
+    /// パレットを適用して RGB 画像に変換する。
+    ///
+    /// 画素値がパレットの範囲外の場合、panic する。
+    pub fn to_rgb(&self, palette: &[Rgb]) -> RgbImage {
+        let pixels = self
+            .pixels
+            .iter()
+            .map(|&idx| palette[usize::from(idx)])
+            .collect();
+
+        RgbImage {
+            width: self.width,
+            height: self.height,
+            pixels,
+        }
+    }
+}
+
+/// RGB 画像。
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RgbImage {
+    width: usize,
+    height: usize,
+    pixels: Vec<Rgb>,
+}
+
+impl RgbImage {
+    /// 全画素が指定した色の画像を作る。
+    pub fn new(width: usize, height: usize, fill: Rgb) -> Self {
+        Self {
+            width,
+            height,
+            pixels: vec![fill; width * height],
+        }
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// 全画素を行優先順で返す。
+    pub fn pixels(&self) -> &[Rgb] {
+        &self.pixels
+    }
+
+    pub fn pixel(&self, x: usize, y: usize) -> Rgb {
+        assert!(x < self.width && y < self.height);
+
+        self.pixels[self.width * y + x]
+    }
+
+    pub fn set_pixel(&mut self, x: usize, y: usize, rgb: Rgb) {
+        assert!(x < self.width && y < self.height);
+
+        self.pixels[self.width * y + x] = rgb;
+    }
+
+    /// PNG ファイルとして保存する。
+    pub fn save_png<P>(&self, path: P) -> anyhow::Result<()>
+    where
+        P: AsRef<Path>,
+    {
+        self._save_png(path.as_ref())
+    }
+
+    fn _save_png(&self, path: &Path) -> anyhow::Result<()> {
+        let file = std::fs::File::create(path)
+            .with_context(|| format!("cannot create '{}'", path.display()))?;
+
+        self.write_png(std::io::BufWriter::new(file))
+    }
+
+    /// PNG 形式で書き出す。
+    pub fn write_png<W>(&self, wtr: W) -> anyhow::Result<()>
+    where
+        W: Write,
+    {
+        ensure!(
+            self.width > 0 && self.height > 0,
+            "cannot write empty image as PNG"
+        );
+
+        let width = u32::try_from(self.width).context("image too wide")?;
+        let height = u32::try_from(self.height).context("image too high")?;
+
+        let mut encoder = png::Encoder::new(wtr, width, height);
+        encoder.set_color(png::ColorType::Rgb);
+        encoder.set_depth(png::BitDepth::Eight);
+
+        let data: Vec<u8> = self
+            .pixels
+            .iter()
+            .flat_map(|rgb| [rgb.r, rgb.g, rgb.b])
+            .collect();
+
+        let mut writer = encoder.write_header()?;
+        writer.write_image_data(&data)?;
+        writer.finish()?;
+
+        Ok(())
+    }
+}
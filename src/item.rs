@@ -5,6 +5,7 @@ use crate::class::Classes;
 use crate::dice::define_dice_expr;
 use crate::element::Elements;
 use crate::monster::MonsterKinds;
+use crate::spell::Spell;
 use crate::string::GameString;
 
 /// アイテム。
@@ -39,6 +40,17 @@ pub struct Item {
     pub slay_monster_kinds: MonsterKinds,
 }
 
+impl Item {
+    /// 使用時の効果の呪文を返す (使用できないアイテムなら `None`)。
+    pub fn use_spell(&self) -> Option<Spell> {
+        if self.usable_in_camp || self.usable_in_battle {
+            Spell::from_id(self.use_spell_id)
+        } else {
+            None
+        }
+    }
+}
+
 /// アイテム種別。
 #[repr(u8)]
 #[derive(Clone, Copy, Debug, Eq, PartialEq, IntoPrimitive, TryFromPrimitive)]
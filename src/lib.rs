@@ -5,22 +5,74 @@
 
 mod alignment;
 pub mod bcd;
+mod breath;
+mod character;
+mod chr;
 mod class;
+mod combat;
 mod dice;
+mod distribution;
+mod drop_table;
 mod element;
+mod encounter;
+mod event;
 pub mod extract;
+mod font;
+mod image;
 mod item;
+mod loadout;
+mod maze;
+mod maze_render;
+mod maze_route;
 mod monster;
+mod monster_attack;
+mod palette;
+mod race;
 mod rng;
 mod rom;
+mod special_power;
+mod spell;
+mod spell_combat;
+mod spell_progression;
+mod stat;
 mod string;
+#[cfg(test)]
+mod test_util;
+mod text;
+mod text_insert;
 pub mod util;
+mod xp;
 
 pub use self::alignment::*;
+pub use self::breath::*;
+pub use self::character::*;
+pub use self::chr::*;
 pub use self::class::*;
+pub use self::combat::*;
+pub use self::distribution::*;
+pub use self::drop_table::*;
 pub use self::element::*;
+pub use self::encounter::*;
+pub use self::event::*;
+pub use self::font::*;
+pub use self::image::*;
 pub use self::item::*;
+pub use self::loadout::*;
+pub use self::maze::*;
+pub use self::maze_render::*;
+pub use self::maze_route::*;
 pub use self::monster::*;
+pub use self::monster_attack::*;
+pub use self::palette::*;
+pub use self::race::*;
 pub use self::rng::*;
 pub use self::rom::*;
+pub use self::special_power::*;
+pub use self::spell::*;
+pub use self::spell_combat::*;
+pub use self::spell_progression::*;
+pub use self::stat::*;
 pub use self::string::*;
+pub use self::text::*;
+pub use self::text_insert::*;
+pub use self::xp::*;
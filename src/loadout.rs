@@ -0,0 +1,388 @@
+use anyhow::ensure;
+use itertools::Itertools as _;
+
+use crate::alignment::Alignment;
+use crate::character::{Character, EquippedItem};
+use crate::combat::{melee_against_target, MeleeAttack};
+use crate::distribution::{dice_distribution, ValueDistribution};
+use crate::element::Elements;
+use crate::item::{Item, ItemKind, ItemMeleeDiceExpr};
+use crate::monster::MonsterKinds;
+
+/// 装備の最適化の目的。
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum LoadoutObjective {
+    /// AC を最小化する。
+    MinAc,
+    /// 1 ターンあたりの打撃ダメージ期待値 (命中率を考慮する) とクリティカル発生率を最大化する。
+    MaxMeleeDamage,
+    /// 指定した属性のうち抵抗できるものの数を最大化する。
+    MaxResistance,
+}
+
+/// 装備の最適化の条件。
+#[derive(Clone, Debug)]
+pub struct LoadoutQuery {
+    /// 装備するキャラクター。職業、性格、レベル、能力値が使われ、現在の装備は無視される。
+    pub character: Character,
+    /// 所持しているアイテムのID (`None` なら全アイテムを候補とする)。
+    pub owned_item_ids: Option<Vec<u8>>,
+    /// 打撃の対象とするモンスター種別 (倍打の判定に使う)。
+    pub target_monster_kinds: MonsterKinds,
+    /// 打撃の対象の AC (命中率の計算に使う)。
+    pub target_ac: i32,
+    /// 抵抗したい属性。
+    pub elements: Elements,
+    /// 最適化の目的 (複数指定するとそれらのパレート最適解を全て求める)。空であってはならない。
+    pub objectives: Vec<LoadoutObjective>,
+}
+
+/// 装備の組み合わせとその評価値。
+#[derive(Clone, Debug, PartialEq)]
+pub struct Loadout {
+    /// 装備するアイテムのID (アイテム種別の昇順)。
+    pub item_ids: Vec<u8>,
+    pub ac: i32,
+    /// `LoadoutQuery::target_ac` の相手に対する 1 ターンあたりの打撃ダメージ期待値。
+    ///
+    /// [`melee_against_target`] による合計ダメージ分布の期待値 (クリティカルによる即死は含まない)。
+    pub expected_melee_damage: f64,
+    /// 同じ相手に対して 1 ターンに 1 回以上クリティカルが発生する確率。
+    pub critical_probability: f64,
+    /// `LoadoutQuery::elements` のうち抵抗できる属性。
+    pub resisted_elements: Elements,
+}
+
+/// 条件を満たす装備の組み合わせのパレート最適解を全て求める。
+///
+/// `items` は `extract_items` で抽出した全アイテム (添字がアイテムID)。
+/// 呪われたアイテム、および性格が合わない性格限定装備は外せなくなるので候補から除く。
+///
+/// 結果は AC の昇順、同じ AC なら打撃ダメージ期待値の降順に並ぶ。
+/// `query.objectives` が空ならエラーを返す (全ての組み合わせが互いに弱支配し合うため)。
+pub fn optimize_loadout(items: &[Item], query: &LoadoutQuery) -> anyhow::Result<Vec<Loadout>> {
+    ensure!(
+        !query.objectives.is_empty(),
+        "at least one objective is required"
+    );
+
+    let mut base = query.character.clone();
+    base.equipment.clear();
+
+    let slots: Vec<Vec<Candidate>> = ItemKind::iter()
+        .filter(|&kind| kind != ItemKind::Tool)
+        .map(|kind| {
+            let candidates = slot_candidates(items, query, &base, kind);
+            prune_dominated(candidates, |a, b| a.weakly_dominates(b, &query.objectives))
+        })
+        .collect();
+
+    let loadouts = slots
+        .into_iter()
+        .multi_cartesian_product()
+        .map(|combo| evaluate(items, query, &base, &combo));
+
+    let mut front = prune_dominated(loadouts.collect(), |a, b| {
+        a.weakly_dominates(b, &query.objectives)
+    });
+    front.sort_by(|a, b| {
+        a.ac.cmp(&b.ac)
+            .then(b.expected_melee_damage.total_cmp(&a.expected_melee_damage))
+            .then(b.critical_probability.total_cmp(&a.critical_probability))
+            .then(b.resisted_elements.bits().cmp(&a.resisted_elements.bits()))
+    });
+
+    Ok(front)
+}
+
+/// あるアイテム種別の装備候補 (何も装備しない場合を含む)。
+#[derive(Clone, Debug)]
+struct Candidate {
+    item_id: Option<u8>,
+    ac: i32,
+    melee_accuracy: i32,
+    extra_melee_count: u32,
+    slay: bool,
+    critical: bool,
+    /// 命中した打撃 1 回のダメージ分布 (倍打を除く)。武器以外では使わない。
+    damage_per_hit: ValueDistribution,
+    resisted_elements: Elements,
+}
+
+impl Candidate {
+    fn weakly_dominates(&self, other: &Self, objectives: &[LoadoutObjective]) -> bool {
+        objectives.iter().all(|objective| match objective {
+            LoadoutObjective::MinAc => self.ac >= other.ac,
+            LoadoutObjective::MaxMeleeDamage => {
+                self.melee_accuracy >= other.melee_accuracy
+                    && self.extra_melee_count >= other.extra_melee_count
+                    && self.slay >= other.slay
+                    && self.critical >= other.critical
+                    && stochastically_dominates(&self.damage_per_hit, &other.damage_per_hit)
+            }
+            LoadoutObjective::MaxResistance => {
+                self.resisted_elements.contains(other.resisted_elements)
+            }
+        })
+    }
+}
+
+fn slot_candidates(
+    items: &[Item],
+    query: &LoadoutQuery,
+    base: &Character,
+    kind: ItemKind,
+) -> Vec<Candidate> {
+    // 武器以外のダメージ分布は比較に影響しないよう全候補で同じにする。
+    let damage_per_hit = |dice_expr: ItemMeleeDiceExpr| {
+        if kind == ItemKind::Weapon {
+            damage_per_hit(base, dice_expr)
+        } else {
+            ValueDistribution::constant(0)
+        }
+    };
+    let none = Candidate {
+        item_id: None,
+        ac: 0,
+        melee_accuracy: 0,
+        extra_melee_count: 0,
+        slay: false,
+        critical: false,
+        damage_per_hit: damage_per_hit(base.damage_dice_expr()),
+        resisted_elements: Elements::default(),
+    };
+
+    let owned = |id: u8| {
+        query
+            .owned_item_ids
+            .as_ref()
+            .is_none_or(|ids| ids.contains(&id))
+    };
+
+    let equippable = items
+        .iter()
+        .enumerate()
+        .filter_map(|(id, item)| Some((u8::try_from(id).ok()?, item)))
+        .filter(|&(id, item)| {
+            item.kind == kind
+                && owned(id)
+                && is_removable(item, base.alignment)
+                && base.can_equip(item).is_ok()
+        })
+        .map(|(id, item)| Candidate {
+            item_id: Some(id),
+            ac: i32::from(item.ac),
+            melee_accuracy: i32::from(item.melee_accuracy),
+            extra_melee_count: u32::from(item.extra_melee_count),
+            slay: !(item.slay_monster_kinds & query.target_monster_kinds).is_empty(),
+            critical: item.critical,
+            damage_per_hit: damage_per_hit(item.melee_dice_expr),
+            resisted_elements: item.element_resistance & query.elements,
+        });
+
+    std::iter::once(none).chain(equippable).collect()
+}
+
+/// 装備しても呪われた状態にならないかどうかを返す。
+fn is_removable(item: &Item, alignment: Alignment) -> bool {
+    !item.cursed && item.alignment.is_none_or(|a| a == alignment)
+}
+
+fn evaluate(
+    items: &[Item],
+    query: &LoadoutQuery,
+    base: &Character,
+    combo: &[Candidate],
+) -> Loadout {
+    let mut chara = base.clone();
+    chara.equipment = combo
+        .iter()
+        .filter_map(|candidate| candidate.item_id)
+        .map(|item_id| EquippedItem {
+            item_id,
+            item: items[usize::from(item_id)].clone(),
+            cursed: false,
+        })
+        .collect();
+
+    let attack = MeleeAttack::from_character(&chara);
+    let outcome = melee_against_target(&attack, query.target_ac, query.target_monster_kinds);
+
+    Loadout {
+        item_ids: chara
+            .equipment
+            .iter()
+            .map(|equipped| equipped.item_id)
+            .collect(),
+        ac: chara.ac(),
+        expected_melee_damage: outcome.damage.mean(),
+        critical_probability: outcome.critical_probability,
+        resisted_elements: chara.element_resistance() & query.elements,
+    }
+}
+
+impl Loadout {
+    fn weakly_dominates(&self, other: &Self, objectives: &[LoadoutObjective]) -> bool {
+        objectives.iter().all(|objective| match objective {
+            LoadoutObjective::MinAc => self.ac <= other.ac,
+            LoadoutObjective::MaxMeleeDamage => {
+                self.expected_melee_damage >= other.expected_melee_damage
+                    && self.critical_probability >= other.critical_probability
+            }
+            LoadoutObjective::MaxResistance => {
+                self.resisted_elements.contains(other.resisted_elements)
+            }
+        })
+    }
+}
+
+/// 他のどれかに弱支配されるものを除く (互いに弱支配し合うものは先にあるものを残す)。
+fn prune_dominated<T>(xs: Vec<T>, weakly_dominates: impl Fn(&T, &T) -> bool) -> Vec<T> {
+    let mut kept = Vec::<T>::new();
+
+    for x in xs {
+        if kept.iter().any(|k| weakly_dominates(k, &x)) {
+            continue;
+        }
+        kept.retain(|k| !weakly_dominates(&x, k));
+        kept.push(x);
+    }
+
+    kept
+}
+
+/// キャラクターが指定したダイス式の武器で打撃を命中させたときの 1 回のダメージ分布を返す
+/// ([`melee_against_target`] と同じく、力による補正を加え 1 未満にはならない)。
+fn damage_per_hit(chara: &Character, dice_expr: ItemMeleeDiceExpr) -> ValueDistribution {
+    dice_distribution(
+        dice_expr.count(),
+        dice_expr.face(),
+        i32::from(dice_expr.bias_decoded()) + chara.damage_bonus(),
+        1,
+    )
+}
+
+/// 分布 `a` が `b` を確率的に弱支配する (全ての値 `v` について `P(a >= v) >= P(b >= v)`) かどうかを返す。
+fn stochastically_dominates(a: &ValueDistribution, b: &ValueDistribution) -> bool {
+    const EPS: f64 = 1e-12;
+
+    (0..=a.max_value().max(b.max_value()))
+        .all(|value| a.probability_at_least(value) + EPS >= b.probability_at_least(value))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::class::Class;
+    use crate::element::Element;
+    use crate::monster::MonsterKind;
+    use crate::test_util::{dummy_character, dummy_item};
+
+    use crate::combat::melee_hit_probability;
+
+    use super::*;
+
+    #[test]
+    fn test_optimize_loadout() {
+        let mut sword = dummy_item(ItemKind::Weapon);
+        sword.melee_dice_expr = ItemMeleeDiceExpr::new(1, 8, 0);
+        let mut slayer = dummy_item(ItemKind::Weapon);
+        slayer.melee_dice_expr = ItemMeleeDiceExpr::new(1, 6, 0);
+        slayer.slay_monster_kinds = MonsterKind::Dragon.into();
+        let mut plate = dummy_item(ItemKind::Armor);
+        plate.ac = 5;
+        let mut robe = dummy_item(ItemKind::Armor);
+        robe.ac = 1;
+        robe.element_resistance = Element::Fire.into();
+        let mut cursed = dummy_item(ItemKind::Armor);
+        cursed.ac = 9;
+        cursed.cursed = true;
+        let items = vec![sword, slayer, plate, robe, cursed];
+
+        let mut query = LoadoutQuery {
+            character: dummy_character(Class::Fighter),
+            owned_item_ids: None,
+            target_monster_kinds: MonsterKind::Dragon.into(),
+            target_ac: 10,
+            elements: Element::Fire.into(),
+            objectives: vec![LoadoutObjective::MinAc, LoadoutObjective::MaxMeleeDamage],
+        };
+
+        let front = optimize_loadout(&items, &query).unwrap();
+        assert_eq!(front.len(), 1);
+        assert_eq!(front[0].item_ids, [1, 2]);
+        assert_eq!(front[0].ac, 5);
+
+        query.objectives.push(LoadoutObjective::MaxResistance);
+        let front = optimize_loadout(&items, &query).unwrap();
+        assert_eq!(front.len(), 2);
+        assert_eq!(front[1].item_ids, [1, 3]);
+
+        query.owned_item_ids = Some(vec![0, 3]);
+        let front = optimize_loadout(&items, &query).unwrap();
+        assert_eq!(front.len(), 1);
+        assert_eq!(front[0].item_ids, [0, 3]);
+    }
+
+    #[test]
+    fn test_optimize_loadout_accuracy() {
+        let mut sword = dummy_item(ItemKind::Weapon);
+        sword.melee_dice_expr = ItemMeleeDiceExpr::new(1, 8, 0);
+        let mut rapier = dummy_item(ItemKind::Weapon);
+        rapier.melee_dice_expr = ItemMeleeDiceExpr::new(1, 4, 0);
+        rapier.melee_accuracy = 5;
+        let items = vec![sword, rapier];
+
+        let mut query = LoadoutQuery {
+            character: dummy_character(Class::Fighter),
+            owned_item_ids: None,
+            target_monster_kinds: MonsterKinds::default(),
+            target_ac: 10,
+            elements: Elements::default(),
+            objectives: vec![LoadoutObjective::MaxMeleeDamage],
+        };
+
+        // 必ず命中する相手にはダイスの大きい武器が良い
+        let front = optimize_loadout(&items, &query).unwrap();
+        assert_eq!(front.len(), 1);
+        assert_eq!(front[0].item_ids, [0]);
+
+        // AC の低い相手には命中補正のある武器が良い
+        query.target_ac = -5;
+        let front = optimize_loadout(&items, &query).unwrap();
+        assert_eq!(front.len(), 1);
+        assert_eq!(front[0].item_ids, [1]);
+        let p_hit = melee_hit_probability(13, -5);
+        let per_hit = dice_distribution(1, 4, 3, 1).mean();
+        assert!((front[0].expected_melee_damage - 3.0 * p_hit * per_hit).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_optimize_loadout_critical() {
+        let mut sword = dummy_item(ItemKind::Weapon);
+        sword.melee_dice_expr = ItemMeleeDiceExpr::new(1, 8, 0);
+        let mut vorpal = dummy_item(ItemKind::Weapon);
+        vorpal.melee_dice_expr = ItemMeleeDiceExpr::new(1, 4, 0);
+        vorpal.critical = true;
+        let items = vec![sword, vorpal];
+
+        let mut query = LoadoutQuery {
+            character: dummy_character(Class::Fighter),
+            owned_item_ids: None,
+            target_monster_kinds: MonsterKinds::default(),
+            target_ac: 10,
+            elements: Elements::default(),
+            objectives: vec![LoadoutObjective::MaxMeleeDamage],
+        };
+
+        // ダメージ期待値とクリティカル発生率のトレードオフになる
+        let front = optimize_loadout(&items, &query).unwrap();
+        assert_eq!(front.len(), 2);
+        assert_eq!(front[0].item_ids, [0]);
+        assert_eq!(front[0].critical_probability, 0.0);
+        assert_eq!(front[1].item_ids, [1]);
+        assert!(front[1].critical_probability > 0.0);
+
+        query.objectives.clear();
+        assert!(optimize_loadout(&items, &query).is_err());
+    }
+}
@@ -0,0 +1,294 @@
+use num_enum::{IntoPrimitive, TryFromPrimitive};
+
+use crate::dice::define_dice_expr;
+
+/// フロアの幅 (東西方向のマス数)。
+pub const FLOOR_WIDTH: usize = 20;
+/// フロアの高さ (南北方向のマス数)。
+pub const FLOOR_HEIGHT: usize = 20;
+
+/// 迷宮の 1 フロア。
+///
+/// 座標 `(x, y)` は南西端を原点とし、x は東向き、y は北向きに増える。
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Floor {
+    cells: Vec<Cell>,
+}
+
+impl Floor {
+    /// `cells` は `FLOOR_WIDTH * FLOOR_HEIGHT` 個のマスを `FLOOR_HEIGHT * x + y` の順に並べたもの。
+    pub fn new(cells: Vec<Cell>) -> Self {
+        assert_eq!(cells.len(), FLOOR_WIDTH * FLOOR_HEIGHT);
+
+        Self { cells }
+    }
+
+    pub fn cell(&self, x: usize, y: usize) -> &Cell {
+        assert!(x < FLOOR_WIDTH && y < FLOOR_HEIGHT);
+
+        &self.cells[FLOOR_HEIGHT * x + y]
+    }
+
+    /// 指定したマスの指定した方向の辺を返す。
+    pub fn edge(&self, x: usize, y: usize, dir: Direction) -> Edge {
+        self.cell(x, y).edge(dir)
+    }
+
+    /// 指定したマスから指定した方向に 1 歩進んだ先の座標を返す (端ではループする)。
+    pub fn neighbor(x: usize, y: usize, dir: Direction) -> (usize, usize) {
+        match dir {
+            Direction::North => (x, (y + 1) % FLOOR_HEIGHT),
+            Direction::East => ((x + 1) % FLOOR_WIDTH, y),
+            Direction::South => (x, (y + FLOOR_HEIGHT - 1) % FLOOR_HEIGHT),
+            Direction::West => ((x + FLOOR_WIDTH - 1) % FLOOR_WIDTH, y),
+        }
+    }
+
+    /// 全マスを `((x, y), マス)` の形で返す。
+    pub fn cells(&self) -> impl Iterator<Item = ((usize, usize), &Cell)> {
+        self.cells
+            .iter()
+            .enumerate()
+            .map(|(i, cell)| ((i / FLOOR_HEIGHT, i % FLOOR_HEIGHT), cell))
+    }
+}
+
+/// 迷宮内の位置。`floor` は地下 1 階を 0 とするインデックス。
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct Position {
+    pub floor: usize,
+    pub x: usize,
+    pub y: usize,
+}
+
+impl Position {
+    pub fn new(floor: usize, x: usize, y: usize) -> Self {
+        Self { floor, x, y }
+    }
+}
+
+/// 迷宮の 1 マス。
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Cell {
+    pub north: Edge,
+    pub east: Edge,
+    pub south: Edge,
+    pub west: Edge,
+    /// 玄室 (入ると必ず戦闘になる) かどうか。
+    pub room: bool,
+    pub special: SpecialSquare,
+}
+
+impl Cell {
+    pub fn edge(&self, dir: Direction) -> Edge {
+        match dir {
+            Direction::North => self.north,
+            Direction::East => self.east,
+            Direction::South => self.south,
+            Direction::West => self.west,
+        }
+    }
+}
+
+/// 方角。
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub enum Direction {
+    North,
+    East,
+    South,
+    West,
+}
+
+impl Direction {
+    /// 正式名称を返す。
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::North => "北",
+            Self::East => "東",
+            Self::South => "南",
+            Self::West => "西",
+        }
+    }
+
+    /// 反対の方角を返す。
+    pub fn opposite(self) -> Self {
+        match self {
+            Self::North => Self::South,
+            Self::East => Self::West,
+            Self::South => Self::North,
+            Self::West => Self::East,
+        }
+    }
+
+    /// 全ての方角を北から時計回りに返す。
+    pub fn iter(
+    ) -> impl DoubleEndedIterator<Item = Self> + ExactSizeIterator + std::iter::FusedIterator + Clone
+    {
+        [Self::North, Self::East, Self::South, Self::West].into_iter()
+    }
+}
+
+/// マスの辺の状態。
+#[repr(u8)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq, IntoPrimitive, TryFromPrimitive)]
+pub enum Edge {
+    Open = 0,
+    Wall = 1,
+    Door = 2,
+    /// 隠し扉。
+    SecretDoor = 3,
+}
+
+impl Edge {
+    /// 正式名称を返す。
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Open => "通路",
+            Self::Wall => "壁",
+            Self::Door => "扉",
+            Self::SecretDoor => "隠し扉",
+        }
+    }
+
+    /// 通行可能かどうかを返す (隠し扉も通行可能とみなす)。
+    pub fn is_passable(self) -> bool {
+        !matches!(self, Self::Wall)
+    }
+}
+
+/// 特殊マス。
+///
+/// 移動先のフロアは 1 始まり (0 は城)。
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum SpecialSquare {
+    Normal,
+    /// 階段。
+    Stairs {
+        floor: u8,
+        x: u8,
+        y: u8,
+    },
+    /// 落とし穴 (ダメージを受ける)。
+    Pit {
+        damage: PitDiceExpr,
+    },
+    /// シュート (別フロアへ落ちる)。
+    Chute {
+        floor: u8,
+        x: u8,
+        y: u8,
+    },
+    /// 回転床。
+    Spinner,
+    /// 暗闇。
+    Darkness,
+    /// テレポーター。
+    Teleporter {
+        floor: u8,
+        x: u8,
+        y: u8,
+    },
+    /// ダメージ床。
+    Ouchy,
+    /// エレベーター。
+    Elevator {
+        floor_top: u8,
+        floor_bottom: u8,
+    },
+    /// 岩 (入れない)。
+    Rock,
+    /// 呪文無効化。
+    AntiMagic,
+    /// メッセージ/イベント。補助値の解釈はイベントの種類による。
+    Message {
+        aux: [i16; 3],
+    },
+    /// 固定の戦闘。
+    Encounter {
+        monster_id: u8,
+        remaining: i16,
+    },
+}
+
+impl SpecialSquare {
+    /// 正式名称を返す。
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Normal => "通常",
+            Self::Stairs { .. } => "階段",
+            Self::Pit { .. } => "落とし穴",
+            Self::Chute { .. } => "シュート",
+            Self::Spinner => "回転床",
+            Self::Darkness => "暗闇",
+            Self::Teleporter { .. } => "テレポーター",
+            Self::Ouchy => "ダメージ床",
+            Self::Elevator { .. } => "エレベーター",
+            Self::Rock => "岩",
+            Self::AntiMagic => "呪文無効化",
+            Self::Message { .. } => "イベント",
+            Self::Encounter { .. } => "固定戦闘",
+        }
+    }
+
+    /// 原作の特殊マス種別コードと補助値から作る。
+    pub fn from_raw(code: u8, aux: [i16; 3]) -> Option<Self> {
+        let [aux0, aux1, aux2] = aux;
+        let byte = |v: i16| v as u8;
+
+        let this = match code {
+            0 => Self::Normal,
+            1 => Self::Stairs {
+                floor: byte(aux0),
+                x: byte(aux1),
+                y: byte(aux2),
+            },
+            2 => Self::Pit {
+                damage: PitDiceExpr::new(byte(aux0), byte(aux1), byte(aux2)),
+            },
+            3 => Self::Chute {
+                floor: byte(aux0),
+                x: byte(aux1),
+                y: byte(aux2),
+            },
+            4 => Self::Spinner,
+            5 => Self::Darkness,
+            6 => Self::Teleporter {
+                floor: byte(aux0),
+                x: byte(aux1),
+                y: byte(aux2),
+            },
+            7 => Self::Ouchy,
+            8 => Self::Elevator {
+                floor_top: byte(aux0),
+                floor_bottom: byte(aux1),
+            },
+            9 => Self::Rock,
+            10 => Self::AntiMagic,
+            11 => Self::Message { aux },
+            12 => Self::Encounter {
+                monster_id: byte(aux1),
+                remaining: aux0,
+            },
+            _ => return None,
+        };
+
+        Some(this)
+    }
+}
+
+// 落とし穴のダメージダイス式。
+define_dice_expr!(PitDiceExpr);
+
+impl std::fmt::Display for PitDiceExpr {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let count = self.count;
+        let face = self.face;
+        let bias = self.bias;
+
+        if bias == 0 {
+            write!(f, "{count}d{face}")
+        } else {
+            write!(f, "{count}d{face}+{bias}")
+        }
+    }
+}
@@ -0,0 +1,584 @@
+use std::fmt::Write as _;
+
+use crate::chr::{TILE_HEIGHT, TILE_WIDTH};
+use crate::font::Font;
+use crate::image::{Rgb, RgbImage};
+use crate::maze::{Direction, Edge, Floor, SpecialSquare, FLOOR_HEIGHT, FLOOR_WIDTH};
+use crate::string::GameChar;
+
+/// 迷宮の描画オプション。
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct MazeRenderOptions {
+    /// 隠し扉を扉として描画するかどうか (偽なら壁として描画する)。
+    pub show_secret_doors: bool,
+    /// パーティの位置と向き。
+    pub party: Option<(usize, usize, Direction)>,
+}
+
+impl MazeRenderOptions {
+    fn edge(&self, edge: Edge) -> Edge {
+        match edge {
+            Edge::SecretDoor if !self.show_secret_doors => Edge::Wall,
+            _ => edge,
+        }
+    }
+
+    fn party_at(&self, x: usize, y: usize) -> Option<Direction> {
+        self.party
+            .and_then(|(px, py, dir)| (px == x && py == y).then_some(dir))
+    }
+}
+
+/// 特殊マスを表す 1 文字の記号を返す (通常のマスなら `None`)。
+pub fn special_square_symbol(special: SpecialSquare) -> Option<char> {
+    let c = match special {
+        SpecialSquare::Normal => return None,
+        SpecialSquare::Stairs { .. } => 'S',
+        SpecialSquare::Pit { .. } => 'P',
+        SpecialSquare::Chute { .. } => 'C',
+        SpecialSquare::Spinner => 'R',
+        SpecialSquare::Darkness => 'D',
+        SpecialSquare::Teleporter { .. } => 'T',
+        SpecialSquare::Ouchy => 'O',
+        SpecialSquare::Elevator { .. } => 'E',
+        SpecialSquare::Rock => '#',
+        SpecialSquare::AntiMagic => 'A',
+        SpecialSquare::Message { .. } => 'M',
+        SpecialSquare::Encounter { .. } => 'F',
+    };
+
+    Some(c)
+}
+
+/// 特殊マスの塗り色を返す。
+pub fn special_square_color(special: SpecialSquare) -> Rgb {
+    let rgb = match special {
+        SpecialSquare::Normal => 0xFFFFFF,
+        SpecialSquare::Stairs { .. } => 0x80E080,
+        SpecialSquare::Pit { .. } => 0xE0A060,
+        SpecialSquare::Chute { .. } => 0xC08040,
+        SpecialSquare::Spinner => 0xE0E060,
+        SpecialSquare::Darkness => 0x606060,
+        SpecialSquare::Teleporter { .. } => 0xC080E0,
+        SpecialSquare::Ouchy => 0xF08080,
+        SpecialSquare::Elevator { .. } => 0x60C0C0,
+        SpecialSquare::Rock => 0x303030,
+        SpecialSquare::AntiMagic => 0x8080F0,
+        SpecialSquare::Message { .. } => 0xF0C0E0,
+        SpecialSquare::Encounter { .. } => 0xF04040,
+    };
+
+    Rgb::from_u32(rgb)
+}
+
+/// 特殊マスの説明 (移動先などを含む) を返す。
+pub fn special_square_description(special: SpecialSquare) -> String {
+    let name = special.name();
+
+    match special {
+        SpecialSquare::Stairs { floor, x, y }
+        | SpecialSquare::Chute { floor, x, y }
+        | SpecialSquare::Teleporter { floor, x, y } => {
+            format!("{name} → {} ({x}, {y})", fmt_floor(floor))
+        }
+        SpecialSquare::Pit { damage } => format!("{name} ({damage})"),
+        SpecialSquare::Elevator {
+            floor_top,
+            floor_bottom,
+        } => format!(
+            "{name} ({}..={})",
+            fmt_floor(floor_top),
+            fmt_floor(floor_bottom)
+        ),
+        SpecialSquare::Message { aux } => format!("{name} {aux:?}"),
+        SpecialSquare::Encounter {
+            monster_id,
+            remaining,
+        } => format!("{name} (モンスター {monster_id}, 残り {remaining})"),
+        _ => name.to_owned(),
+    }
+}
+
+fn fmt_floor(floor: u8) -> String {
+    if floor == 0 {
+        "城".to_owned()
+    } else {
+        format!("B{floor}F")
+    }
+}
+
+fn party_symbol(dir: Direction) -> char {
+    match dir {
+        Direction::North => '^',
+        Direction::East => '>',
+        Direction::South => 'v',
+        Direction::West => '<',
+    }
+}
+
+/// 迷宮をテキストで描画する。北が上。
+///
+/// 各マスは横 4 文字、縦 2 行で、壁は `-`/`|`、扉は `d`、隠し扉は `s` で表す (特殊マスの記号は大文字)。
+/// 隣のマスから見た状態と一致しない辺は `!` で表す。
+/// 迷宮は端でループするので、外周の辺は反対側の端の辺と同じものになる。
+pub fn render_maze_ascii(floor: &Floor, opts: &MazeRenderOptions) -> String {
+    let mut s = String::new();
+
+    // マス (x, y) の北側の辺
+    let north_line = |s: &mut String, y: usize| {
+        for x in 0..FLOOR_WIDTH {
+            let (nx, ny) = Floor::neighbor(x, y, Direction::North);
+            let edge = shared_edge(
+                opts.edge(floor.edge(x, y, Direction::North)),
+                opts.edge(floor.edge(nx, ny, Direction::South)),
+            );
+            s.push('+');
+            s.push_str(ascii_horizontal(edge));
+        }
+        s.push_str("+\n");
+    };
+
+    for y in (0..FLOOR_HEIGHT).rev() {
+        north_line(&mut s, y);
+
+        // マス本体 (各マスの西側の辺を含む)
+        for x in 0..FLOOR_WIDTH {
+            let cell = floor.cell(x, y);
+            let (wx, wy) = Floor::neighbor(x, y, Direction::West);
+            let west = shared_edge(
+                opts.edge(cell.west),
+                opts.edge(floor.edge(wx, wy, Direction::East)),
+            );
+            s.push(ascii_vertical(west));
+            let room = if cell.room { '*' } else { ' ' };
+            let center = opts
+                .party_at(x, y)
+                .map(party_symbol)
+                .or_else(|| special_square_symbol(cell.special))
+                .unwrap_or(' ');
+            s.push(room);
+            s.push(center);
+            s.push(' ');
+        }
+        // 最東端の辺は最西端の辺と同じ
+        let east = shared_edge(
+            opts.edge(floor.edge(FLOOR_WIDTH - 1, y, Direction::East)),
+            opts.edge(floor.edge(0, y, Direction::West)),
+        );
+        s.push(ascii_vertical(east));
+        s.push('\n');
+    }
+
+    // 最南端の辺は最北端の辺と同じ
+    north_line(&mut s, FLOOR_HEIGHT - 1);
+
+    s
+}
+
+/// 隣り合うマスそれぞれから見た辺が一致すればその辺を返す。
+fn shared_edge(edge: Edge, other: Edge) -> Option<Edge> {
+    (edge == other).then_some(edge)
+}
+
+fn ascii_horizontal(edge: Option<Edge>) -> &'static str {
+    match edge {
+        Some(Edge::Open) => "   ",
+        Some(Edge::Wall) => "---",
+        Some(Edge::Door) => "-d-",
+        Some(Edge::SecretDoor) => "-s-",
+        None => "-!-",
+    }
+}
+
+fn ascii_vertical(edge: Option<Edge>) -> char {
+    match edge {
+        Some(Edge::Open) => ' ',
+        Some(Edge::Wall) => '|',
+        Some(Edge::Door) => 'd',
+        Some(Edge::SecretDoor) => 's',
+        None => '!',
+    }
+}
+
+/// SVG での 1 マスの大きさ。
+const SVG_CELL: usize = 24;
+/// SVG の外周の余白 (外周の扉がはみ出さないように)。
+const SVG_MARGIN: usize = 4;
+
+/// 迷宮を SVG で描画する。北が上。
+///
+/// 特殊マスは色分けされ、記号と説明 (`<title>`) が付く。
+pub fn render_maze_svg(floor: &Floor, opts: &MazeRenderOptions) -> String {
+    let size_w = SVG_CELL * FLOOR_WIDTH + 2 * SVG_MARGIN;
+    let size_h = SVG_CELL * FLOOR_HEIGHT + 2 * SVG_MARGIN;
+
+    let mut s = String::new();
+
+    writeln!(
+        s,
+        r#"<svg xmlns="http://www.w3.org/2000/svg" width="{size_w}" height="{size_h}" viewBox="0 0 {size_w} {size_h}">"#
+    )
+    .unwrap();
+    writeln!(
+        s,
+        r#"<rect width="{size_w}" height="{size_h}" fill="white"/>"#
+    )
+    .unwrap();
+
+    for ((x, y), cell) in floor.cells() {
+        let (left, top) = svg_origin(x, y);
+
+        if cell.special != SpecialSquare::Normal || cell.room {
+            let fill = if cell.special == SpecialSquare::Normal {
+                Rgb::from_u32(0xF0F0F0)
+            } else {
+                special_square_color(cell.special)
+            };
+            writeln!(
+                s,
+                r#"<rect x="{left}" y="{top}" width="{SVG_CELL}" height="{SVG_CELL}" fill="{}"><title>({x}, {y}) {}{}</title></rect>"#,
+                svg_color(fill),
+                special_square_description(cell.special),
+                if cell.room { " 玄室" } else { "" },
+            )
+            .unwrap();
+        }
+
+        if let Some(c) = special_square_symbol(cell.special) {
+            writeln!(
+                s,
+                r#"<text x="{}" y="{}" font-family="monospace" font-size="{}" text-anchor="middle">{c}</text>"#,
+                left + SVG_CELL / 2,
+                top + SVG_CELL * 2 / 3,
+                SVG_CELL / 2,
+            )
+            .unwrap();
+        }
+
+        for dir in Direction::iter() {
+            write_svg_edge(&mut s, x, y, dir, opts.edge(cell.edge(dir)));
+        }
+    }
+
+    if let Some((x, y, dir)) = opts.party {
+        let (left, top) = svg_origin(x, y);
+        let (cx, cy) = (left + SVG_CELL / 2, top + SVG_CELL / 2);
+        let r = SVG_CELL / 3;
+        let rotate = match dir {
+            Direction::North => 0,
+            Direction::East => 90,
+            Direction::South => 180,
+            Direction::West => 270,
+        };
+        writeln!(
+            s,
+            r#"<polygon points="{cx},{} {},{} {},{}" fill="blue" transform="rotate({rotate} {cx} {cy})"/>"#,
+            cy - r,
+            cx + r,
+            cy + r,
+            cx - r,
+            cy + r,
+        )
+        .unwrap();
+    }
+
+    s.push_str("</svg>\n");
+
+    s
+}
+
+fn svg_origin(x: usize, y: usize) -> (usize, usize) {
+    (
+        SVG_MARGIN + SVG_CELL * x,
+        SVG_MARGIN + SVG_CELL * (FLOOR_HEIGHT - 1 - y),
+    )
+}
+
+fn svg_color(rgb: Rgb) -> String {
+    format!("#{:02X}{:02X}{:02X}", rgb.r, rgb.g, rgb.b)
+}
+
+fn write_svg_edge(s: &mut String, x: usize, y: usize, dir: Direction, edge: Edge) {
+    if edge == Edge::Open {
+        return;
+    }
+
+    let (left, top) = svg_origin(x, y);
+    let (right, bottom) = (left + SVG_CELL, top + SVG_CELL);
+
+    let (x1, y1, x2, y2) = match dir {
+        Direction::North => (left, top, right, top),
+        Direction::East => (right, top, right, bottom),
+        Direction::South => (left, bottom, right, bottom),
+        Direction::West => (left, top, left, bottom),
+    };
+
+    writeln!(
+        s,
+        r#"<line x1="{x1}" y1="{y1}" x2="{x2}" y2="{y2}" stroke="black" stroke-width="2"/>"#
+    )
+    .unwrap();
+
+    if matches!(edge, Edge::Door | Edge::SecretDoor) {
+        let color = if edge == Edge::Door { "orange" } else { "red" };
+        let (mx, my) = ((x1 + x2) / 2, (y1 + y2) / 2);
+        let half = SVG_CELL / 4;
+        writeln!(
+            s,
+            r#"<rect x="{}" y="{}" width="{}" height="{}" fill="{color}" stroke="black"/>"#,
+            mx - half / 2,
+            my - half / 2,
+            half,
+            half,
+        )
+        .unwrap();
+    }
+}
+
+/// 画像での 1 マスの大きさ (ピクセル)。フォントのグリフが収まるように 2 タイル分とする。
+const IMAGE_CELL: usize = 2 * TILE_WIDTH;
+
+/// 迷宮を画像として描画する。北が上。
+///
+/// `font` を指定した場合、特殊マスに原作のフォントで記号を描く。
+pub fn render_maze_image(floor: &Floor, opts: &MazeRenderOptions, font: Option<&Font>) -> RgbImage {
+    const WALL: Rgb = Rgb::from_u32(0x000000);
+    const DOOR: Rgb = Rgb::from_u32(0xF0A000);
+    const SECRET_DOOR: Rgb = Rgb::from_u32(0xE00000);
+    const ROOM: Rgb = Rgb::from_u32(0xF0F0F0);
+    const PARTY: Rgb = Rgb::from_u32(0x0000F0);
+
+    let mut image = RgbImage::new(
+        IMAGE_CELL * FLOOR_WIDTH + 1,
+        IMAGE_CELL * FLOOR_HEIGHT + 1,
+        Rgb::from_u32(0xFFFFFF),
+    );
+
+    for ((x, y), cell) in floor.cells() {
+        let left = IMAGE_CELL * x;
+        let top = IMAGE_CELL * (FLOOR_HEIGHT - 1 - y);
+
+        let fill = match cell.special {
+            SpecialSquare::Normal if cell.room => Some(ROOM),
+            SpecialSquare::Normal => None,
+            special => Some(special_square_color(special)),
+        };
+        if let Some(fill) = fill {
+            fill_rect(
+                &mut image,
+                left + 1,
+                top + 1,
+                IMAGE_CELL - 1,
+                IMAGE_CELL - 1,
+                fill,
+            );
+        }
+
+        if let (Some(font), Some(c)) = (font, special_square_symbol(cell.special)) {
+            if let Some(ch) = GameChar::from_char(c) {
+                let glyph = font.glyph(ch);
+                let (gx, gy) = (left + TILE_WIDTH / 2, top + TILE_HEIGHT / 2);
+                for dy in 0..TILE_HEIGHT {
+                    for dx in 0..TILE_WIDTH {
+                        if glyph.pixel(dx, dy) != 0 {
+                            image.set_pixel(gx + dx, gy + dy, WALL);
+                        }
+                    }
+                }
+            }
+        }
+
+        if let Some(dir) = opts.party_at(x, y) {
+            draw_party(&mut image, left, top, dir, PARTY);
+        }
+
+        for dir in Direction::iter() {
+            let edge = opts.edge(cell.edge(dir));
+            if edge == Edge::Open {
+                continue;
+            }
+            let (ex, ey, horizontal) = match dir {
+                Direction::North => (left, top, true),
+                Direction::East => (left + IMAGE_CELL, top, false),
+                Direction::South => (left, top + IMAGE_CELL, true),
+                Direction::West => (left, top, false),
+            };
+            for i in 0..=IMAGE_CELL {
+                let (px, py) = if horizontal {
+                    (ex + i, ey)
+                } else {
+                    (ex, ey + i)
+                };
+                let door = (IMAGE_CELL / 4..=IMAGE_CELL * 3 / 4).contains(&i);
+                let color = match edge {
+                    Edge::Door if door => DOOR,
+                    Edge::SecretDoor if door => SECRET_DOOR,
+                    _ => WALL,
+                };
+                image.set_pixel(px, py, color);
+            }
+        }
+    }
+
+    image
+}
+
+fn fill_rect(image: &mut RgbImage, x: usize, y: usize, w: usize, h: usize, rgb: Rgb) {
+    for py in y..y + h {
+        for px in x..x + w {
+            image.set_pixel(px, py, rgb);
+        }
+    }
+}
+
+/// パーティの向きを表す三角形を描く。
+fn draw_party(image: &mut RgbImage, left: usize, top: usize, dir: Direction, rgb: Rgb) {
+    let n = IMAGE_CELL - 4;
+
+    for i in 0..n {
+        // 頂点からの距離 i に応じて幅を広げる
+        let half = i / 2;
+        let mid = n / 2;
+        for j in mid.saturating_sub(half)..=(mid + half).min(n - 1) {
+            let (dx, dy) = match dir {
+                Direction::North => (j, i),
+                Direction::South => (j, n - 1 - i),
+                Direction::West => (i, j),
+                Direction::East => (n - 1 - i, j),
+            };
+            image.set_pixel(left + 2 + dx, top + 2 + dy, rgb);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::maze::Cell;
+
+    fn boxed_floor() -> Floor {
+        let cells = (0..FLOOR_WIDTH * FLOOR_HEIGHT)
+            .map(|_| Cell {
+                north: Edge::Wall,
+                east: Edge::SecretDoor,
+                south: Edge::Wall,
+                west: Edge::SecretDoor,
+                room: false,
+                special: SpecialSquare::Normal,
+            })
+            .collect();
+
+        Floor::new(cells)
+    }
+
+    fn set_cell(floor: Floor, x: usize, y: usize, f: impl FnOnce(&mut Cell)) -> Floor {
+        let mut cells: Vec<Cell> = floor.cells().map(|(_, &cell)| cell).collect();
+        f(&mut cells[FLOOR_HEIGHT * x + y]);
+
+        Floor::new(cells)
+    }
+
+    #[test]
+    fn test_render_maze_ascii() {
+        let mut floor = boxed_floor();
+
+        let hidden = render_maze_ascii(&floor, &MazeRenderOptions::default());
+        assert!(!hidden.contains('s'));
+        assert!(!hidden.contains('!'));
+
+        let opts = MazeRenderOptions {
+            show_secret_doors: true,
+            party: Some((0, 19, Direction::East)),
+        };
+        let shown = render_maze_ascii(&floor, &opts);
+        let lines: Vec<&str> = shown.lines().collect();
+        assert_eq!(lines.len(), 2 * FLOOR_HEIGHT + 1);
+        assert_eq!(lines[0].len(), 4 * FLOOR_WIDTH + 1);
+        assert!(lines[1].starts_with("s > s"));
+        assert_eq!(lines[0], lines[2 * FLOOR_HEIGHT]);
+
+        // 特殊マスの記号と辺の記号は区別できる
+        floor = set_cell(floor, 1, 19, |cell| {
+            cell.special = SpecialSquare::Stairs {
+                floor: 0,
+                x: 0,
+                y: 0,
+            }
+        });
+        let lines: Vec<String> = render_maze_ascii(&floor, &opts)
+            .lines()
+            .map(str::to_owned)
+            .collect();
+        assert!(lines[1].starts_with("s > s S s"));
+    }
+
+    #[test]
+    fn test_render_maze_ascii_one_sided_edges() {
+        // (3, 5) の南側のみ扉で、(3, 4) から見た北側は壁
+        let floor = set_cell(boxed_floor(), 3, 5, |cell| cell.south = Edge::Door);
+
+        let ascii = render_maze_ascii(&floor, &MazeRenderOptions::default());
+        let lines: Vec<&str> = ascii.lines().collect();
+
+        // y = 5 の行の下 (y = 4 の北側) の辺
+        let line = lines[2 * (FLOOR_HEIGHT - 1 - 4)];
+        assert_eq!(&line[4 * 3..][..5], "+-!-+");
+        assert_eq!(ascii.matches('!').count(), 1);
+    }
+
+    #[test]
+    fn test_render_maze_svg() {
+        let floor = set_cell(boxed_floor(), 3, 4, |cell| {
+            cell.special = SpecialSquare::Stairs {
+                floor: 0,
+                x: 0,
+                y: 0,
+            }
+        });
+        let opts = MazeRenderOptions {
+            show_secret_doors: true,
+            party: Some((0, 0, Direction::North)),
+        };
+
+        let svg = render_maze_svg(&floor, &opts);
+
+        assert!(svg.starts_with("<svg "));
+        assert!(svg.ends_with("</svg>\n"));
+        assert!(svg.contains("<title>(3, 4) 階段 → 城 (0, 0)</title>"));
+        assert!(svg.contains("<polygon "));
+        assert_eq!(
+            svg.matches("<line ").count(),
+            4 * FLOOR_WIDTH * FLOOR_HEIGHT
+        );
+        assert_eq!(
+            svg.matches(r#"fill="red""#).count(),
+            2 * FLOOR_WIDTH * FLOOR_HEIGHT
+        );
+    }
+
+    #[test]
+    fn test_render_maze_image() {
+        let floor = set_cell(boxed_floor(), 3, 4, |cell| {
+            cell.special = SpecialSquare::Rock
+        });
+        let opts = MazeRenderOptions::default();
+
+        let image = render_maze_image(&floor, &opts, None);
+
+        assert_eq!(image.width(), IMAGE_CELL * FLOOR_WIDTH + 1);
+        assert_eq!(image.height(), IMAGE_CELL * FLOOR_HEIGHT + 1);
+        // 左上の角は壁
+        assert_eq!(image.pixel(0, 0), Rgb::from_u32(0x000000));
+        // (3, 4) の内部は岩の色
+        let (left, top) = (IMAGE_CELL * 3, IMAGE_CELL * (FLOOR_HEIGHT - 1 - 4));
+        assert_eq!(
+            image.pixel(left + IMAGE_CELL / 2, top + IMAGE_CELL / 2),
+            special_square_color(SpecialSquare::Rock)
+        );
+
+        let mut png = Vec::<u8>::new();
+        image.write_png(&mut png).unwrap();
+        assert!(png.starts_with(b"\x89PNG\r\n\x1A\n"));
+    }
+}
@@ -0,0 +1,451 @@
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap};
+
+use crate::maze::{Direction, Edge, Floor, Position, SpecialSquare, FLOOR_HEIGHT, FLOOR_WIDTH};
+
+impl Position {
+    /// 特殊マスの移動先 (フロアは 1 始まり、0 は城) から作る。
+    ///
+    /// 城の場合や、座標がフロアの範囲外の場合は `None` を返す。
+    fn from_dest(floor: u8, x: u8, y: u8) -> Option<Self> {
+        let (x, y) = (usize::from(x), usize::from(y));
+        (floor != 0 && x < FLOOR_WIDTH && y < FLOOR_HEIGHT)
+            .then(|| Self::new(usize::from(floor - 1), x, y))
+    }
+}
+
+/// 経路探索のオプション。
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct RouteOptions {
+    /// 隠し扉を通行可能とみなすかどうか。
+    pub use_secret_doors: bool,
+    /// MALOR による転移のコスト (歩数換算)。`None` なら MALOR を使わない。
+    pub malor_cost: Option<u32>,
+}
+
+impl Default for RouteOptions {
+    fn default() -> Self {
+        Self {
+            use_secret_doors: true,
+            malor_cost: None,
+        }
+    }
+}
+
+/// 経路の 1 手。
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct RouteStep {
+    pub action: RouteAction,
+    /// この手を実行した後の位置。
+    pub position: Position,
+    /// 移動後の位置が暗闇かどうか (地図で位置を確認できない)。
+    pub dark: bool,
+    /// 回転床の上から出発する手かどうか。
+    ///
+    /// 回転床で向きが変わるので、この手の方角は向きを確認してから (DUMAPIC 等) 進む必要がある。
+    pub after_spinner: bool,
+}
+
+/// 経路の 1 手の内容。
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum RouteAction {
+    /// 指定した方角へ 1 歩進む。
+    Move(Direction),
+    /// 階段を使う。
+    Stairs,
+    /// エレベーターで指定したフロアへ移動する。
+    Elevator { floor: usize },
+    /// テレポーターにより強制的に転移した。
+    Teleported,
+    /// シュートにより強制的に落下した。
+    Chute,
+    /// MALOR で転移する。
+    Malor,
+}
+
+impl std::fmt::Display for RouteStep {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let Position { floor, x, y } = self.position;
+
+        match self.action {
+            RouteAction::Move(dir) => write!(f, "{}へ進む", dir.name())?,
+            RouteAction::Stairs => f.write_str("階段を使う")?,
+            RouteAction::Elevator { floor } => write!(f, "エレベーターで B{}F へ", floor + 1)?,
+            RouteAction::Teleported => f.write_str("テレポーターで転移")?,
+            RouteAction::Chute => f.write_str("シュートで落下")?,
+            RouteAction::Malor => f.write_str("MALOR で転移")?,
+        }
+
+        write!(f, " → B{}F ({x}, {y})", floor + 1)?;
+
+        if self.dark {
+            f.write_str(" [暗闇]")?;
+        }
+        if self.after_spinner {
+            f.write_str(" [回転床の直後]")?;
+        }
+
+        Ok(())
+    }
+}
+
+/// 2 地点間の最短経路を求める。到達不能なら `None` を返す。
+///
+/// 一方通行の扉 (片側からのみ通行可能な辺) やテレポーター/シュートによる強制移動を考慮する。
+/// 城へ出てしまうテレポーター/シュート (および移動先が不正なもの) には入らない。
+/// 経路は絶対方角で表し、パーティの向きはモデル化しない。回転床で向きが変わった直後の手には
+/// [`RouteStep::after_spinner`] を、暗闇に入る手には [`RouteStep::dark`] を立てるので、
+/// 実際に辿る際はそこで向きや位置を確認すること。
+pub fn find_route(
+    floors: &[Floor],
+    start: Position,
+    goal: Position,
+    opts: &RouteOptions,
+) -> Option<Vec<RouteStep>> {
+    let mut dist = HashMap::<Position, u32>::new();
+    let mut prev = HashMap::<Position, (Position, RouteStep)>::new();
+    let mut heap = BinaryHeap::<Reverse<(u32, Position)>>::new();
+
+    dist.insert(start, 0);
+    heap.push(Reverse((0, start)));
+
+    while let Some(Reverse((d, pos))) = heap.pop() {
+        if pos == goal {
+            break;
+        }
+        if dist.get(&pos).is_some_and(|&best| d > best) {
+            continue;
+        }
+
+        for (cost, step) in successors(floors, pos, goal, opts) {
+            let nd = d + cost;
+            if dist.get(&step.position).is_none_or(|&best| nd < best) {
+                dist.insert(step.position, nd);
+                prev.insert(step.position, (pos, step));
+                heap.push(Reverse((nd, step.position)));
+            }
+        }
+    }
+
+    if !dist.contains_key(&goal) {
+        return None;
+    }
+
+    let mut steps = Vec::<RouteStep>::new();
+    let mut cur = goal;
+    while cur != start {
+        let (p, step) = prev[&cur];
+        steps.push(step);
+        cur = p;
+    }
+    steps.reverse();
+
+    Some(steps)
+}
+
+/// `pos` から 1 手で行ける (コスト, 手) を列挙する。
+fn successors(
+    floors: &[Floor],
+    pos: Position,
+    goal: Position,
+    opts: &RouteOptions,
+) -> Vec<(u32, RouteStep)> {
+    let floor = &floors[pos.floor];
+    let cell = floor.cell(pos.x, pos.y);
+
+    let mut res = Vec::<(u32, RouteStep)>::new();
+
+    for dir in Direction::iter() {
+        let passable = match cell.edge(dir) {
+            Edge::SecretDoor => opts.use_secret_doors,
+            edge => edge.is_passable(),
+        };
+        if !passable {
+            continue;
+        }
+        let (x, y) = Floor::neighbor(pos.x, pos.y, dir);
+        if floor.cell(x, y).special == SpecialSquare::Rock {
+            continue;
+        }
+        let step = enter(
+            floors,
+            Position::new(pos.floor, x, y),
+            RouteAction::Move(dir),
+        );
+        res.extend(step.map(|step| (1, step)));
+    }
+
+    // 回転床の上では向きが不定なので、ここから歩く手には印を付ける。
+    if cell.special == SpecialSquare::Spinner {
+        for (_, step) in &mut res {
+            step.after_spinner = true;
+        }
+    }
+
+    match cell.special {
+        SpecialSquare::Stairs { floor, x, y } => {
+            if let Some(dest) = Position::from_dest(floor, x, y) {
+                if dest.floor < floors.len() {
+                    res.extend(enter(floors, dest, RouteAction::Stairs).map(|step| (1, step)));
+                }
+            }
+        }
+        SpecialSquare::Elevator {
+            floor_top,
+            floor_bottom,
+        } => {
+            for floor in floor_top.max(1)..=floor_bottom {
+                let dest = Position::new(usize::from(floor - 1), pos.x, pos.y);
+                if dest.floor < floors.len() && dest.floor != pos.floor {
+                    let action = RouteAction::Elevator { floor: dest.floor };
+                    res.extend(enter(floors, dest, action).map(|step| (1, step)));
+                }
+            }
+        }
+        _ => {}
+    }
+
+    if let Some(cost) = opts.malor_cost {
+        // 転移先は任意に選べるので、目的地へ直接転移するのが最善。
+        if cell.special != SpecialSquare::AntiMagic {
+            res.extend(enter(floors, goal, RouteAction::Malor).map(|step| (cost, step)));
+        }
+    }
+
+    res
+}
+
+/// `dest` に入ったときの手を返す。テレポーター/シュートの場合、強制移動後の位置になる。
+///
+/// 強制移動で迷宮から出てしまう (城へ移動する、移動先が不正、強制移動が循環する) 場合は `None` を返す。
+fn enter(floors: &[Floor], dest: Position, action: RouteAction) -> Option<RouteStep> {
+    let settle = |pos: Position, action: RouteAction| RouteStep {
+        action,
+        position: pos,
+        dark: floors[pos.floor].cell(pos.x, pos.y).special == SpecialSquare::Darkness,
+        after_spinner: false,
+    };
+
+    let mut pos = dest;
+    let mut action = action;
+
+    // 強制移動が連鎖しても無限ループしないよう、回数を制限する。
+    for _ in 0..FLOOR_WIDTH * FLOOR_HEIGHT {
+        let special = floors[pos.floor].cell(pos.x, pos.y).special;
+        let (next, forced) = match special {
+            SpecialSquare::Teleporter { floor, x, y } => {
+                (Position::from_dest(floor, x, y), RouteAction::Teleported)
+            }
+            SpecialSquare::Chute { floor, x, y } => {
+                (Position::from_dest(floor, x, y), RouteAction::Chute)
+            }
+            _ => return Some(settle(pos, action)),
+        };
+        let next = next.filter(|next| next.floor < floors.len())?;
+        if next == pos {
+            return Some(settle(pos, action));
+        }
+        pos = next;
+        action = forced;
+    }
+
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::maze::Cell;
+
+    fn open_floor() -> Vec<Cell> {
+        (0..FLOOR_WIDTH * FLOOR_HEIGHT)
+            .map(|_| Cell {
+                north: Edge::Open,
+                east: Edge::Open,
+                south: Edge::Open,
+                west: Edge::Open,
+                room: false,
+                special: SpecialSquare::Normal,
+            })
+            .collect()
+    }
+
+    fn idx(x: usize, y: usize) -> usize {
+        FLOOR_HEIGHT * x + y
+    }
+
+    #[test]
+    fn test_find_route_walk() {
+        let floors = [Floor::new(open_floor())];
+
+        let route = find_route(
+            &floors,
+            Position::new(0, 0, 0),
+            Position::new(0, 2, 1),
+            &RouteOptions::default(),
+        )
+        .unwrap();
+
+        assert_eq!(route.len(), 3);
+        assert_eq!(route.last().unwrap().position, Position::new(0, 2, 1));
+
+        // 端はループする
+        let route = find_route(
+            &floors,
+            Position::new(0, 0, 0),
+            Position::new(0, 19, 0),
+            &RouteOptions::default(),
+        )
+        .unwrap();
+        assert_eq!(
+            route,
+            [enter(
+                &floors,
+                Position::new(0, 19, 0),
+                RouteAction::Move(Direction::West)
+            )
+            .unwrap()]
+        );
+    }
+
+    #[test]
+    fn test_find_route_teleporter_and_malor() {
+        let mut cells0 = open_floor();
+        cells0[idx(1, 0)].special = SpecialSquare::Teleporter {
+            floor: 2,
+            x: 5,
+            y: 5,
+        };
+        let mut cells1 = open_floor();
+        cells1[idx(5, 5)].special = SpecialSquare::Darkness;
+        // (5, 5) からは北にしか出られない
+        cells1[idx(5, 5)].east = Edge::Wall;
+        cells1[idx(5, 5)].south = Edge::Wall;
+        cells1[idx(5, 5)].west = Edge::Wall;
+        let floors = [Floor::new(cells0), Floor::new(cells1)];
+
+        let route = find_route(
+            &floors,
+            Position::new(0, 0, 0),
+            Position::new(1, 5, 7),
+            &RouteOptions::default(),
+        )
+        .unwrap();
+
+        assert_eq!(route.len(), 3);
+        assert_eq!(route[0].action, RouteAction::Teleported);
+        assert!(route[0].dark);
+        assert!(route[1..]
+            .iter()
+            .all(|step| step.action == RouteAction::Move(Direction::North)));
+
+        let opts = RouteOptions {
+            malor_cost: Some(1),
+            ..Default::default()
+        };
+        let route = find_route(
+            &floors,
+            Position::new(0, 0, 0),
+            Position::new(1, 15, 15),
+            &opts,
+        )
+        .unwrap();
+        assert_eq!(route.len(), 1);
+        assert_eq!(route[0].action, RouteAction::Malor);
+    }
+
+    #[test]
+    fn test_find_route_spinner() {
+        let mut cells = open_floor();
+        cells[idx(1, 0)].special = SpecialSquare::Spinner;
+        let floors = [Floor::new(cells)];
+
+        let route = find_route(
+            &floors,
+            Position::new(0, 0, 0),
+            Position::new(0, 2, 0),
+            &RouteOptions::default(),
+        )
+        .unwrap();
+
+        assert_eq!(route.len(), 2);
+        assert!(!route[0].after_spinner);
+        assert!(route[1].after_spinner);
+    }
+
+    #[test]
+    fn test_find_route_avoids_exit_to_castle() {
+        let mut cells = open_floor();
+        cells[idx(1, 0)].special = SpecialSquare::Chute {
+            floor: 0,
+            x: 0,
+            y: 0,
+        };
+        cells[idx(0, 1)].special = SpecialSquare::Teleporter {
+            floor: 0,
+            x: 0,
+            y: 0,
+        };
+        let floors = [Floor::new(cells)];
+
+        assert_eq!(
+            enter(
+                &floors,
+                Position::new(0, 1, 0),
+                RouteAction::Move(Direction::East)
+            ),
+            None
+        );
+
+        let route = find_route(
+            &floors,
+            Position::new(0, 0, 0),
+            Position::new(0, 2, 0),
+            &RouteOptions::default(),
+        )
+        .unwrap();
+
+        assert!(route
+            .iter()
+            .all(|step| matches!(step.action, RouteAction::Move(_))));
+        assert!(route
+            .iter()
+            .all(|step| step.position != Position::new(0, 1, 0)
+                && step.position != Position::new(0, 0, 1)));
+        assert_eq!(route.last().unwrap().position, Position::new(0, 2, 0));
+    }
+
+    #[test]
+    fn test_enter_teleporter_loop() {
+        let mut cells = open_floor();
+        cells[idx(1, 0)].special = SpecialSquare::Teleporter {
+            floor: 1,
+            x: 2,
+            y: 0,
+        };
+        cells[idx(2, 0)].special = SpecialSquare::Teleporter {
+            floor: 1,
+            x: 1,
+            y: 0,
+        };
+        let floors = [Floor::new(cells)];
+
+        assert_eq!(
+            enter(
+                &floors,
+                Position::new(0, 1, 0),
+                RouteAction::Move(Direction::East)
+            ),
+            None
+        );
+    }
+
+    #[test]
+    fn test_position_from_dest() {
+        assert_eq!(Position::from_dest(0, 1, 2), None);
+        assert_eq!(Position::from_dest(3, 1, 2), Some(Position::new(2, 1, 2)));
+        assert_eq!(Position::from_dest(1, 20, 0), None);
+        assert_eq!(Position::from_dest(1, 0, 20), None);
+    }
+}
@@ -2,6 +2,9 @@ use flagset::{flags, FlagSet};
 
 use crate::dice::define_dice_expr;
 use crate::element::Elements;
+use crate::image::{IndexedImage, Rgb, RgbImage};
+use crate::palette::MasterPalette;
+use crate::spell::{Spell, SpellSchool};
 use crate::string::GameString;
 
 /// モンスター。
@@ -32,6 +35,21 @@ pub struct Monster {
     pub melee_dice_exprs: Vec<MonsterMeleeDiceExpr>,
 }
 
+impl Monster {
+    /// 唱えうる呪文 (各系統の呪文レベル以下の全呪文) を昇順で返す。
+    pub fn castable_spells(&self) -> Vec<Spell> {
+        Spell::iter()
+            .filter(|spell| {
+                let lv = match spell.school() {
+                    SpellSchool::Mage => self.mage_spell_lv,
+                    SpellSchool::Cleric => self.cleric_spell_lv,
+                };
+                spell.level() <= lv
+            })
+            .collect()
+    }
+}
+
 flags! {
     /// モンスター種別。
     #[repr(u16)]
@@ -417,3 +435,24 @@ fn decode_spawn_hp_bias(bias: u8) -> i16 {
         i16::from(bias as i8)
     }
 }
+
+/// モンスターの戦闘グラフィック。
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct MonsterGraphic {
+    /// 画素値が `0..=3` の画像。
+    pub image: IndexedImage,
+    /// サブパレット (NES のマスターパレットにおける色インデックス 4 個)。
+    pub palette: [u8; 4],
+}
+
+impl MonsterGraphic {
+    /// 指定した RGB サブパレットを適用した画像を返す。
+    pub fn to_rgb(&self, palette: &[Rgb; 4]) -> RgbImage {
+        self.image.to_rgb(palette)
+    }
+
+    /// 自身のサブパレットを指定したマスターパレットで RGB に変換し、適用した画像を返す。
+    pub fn render(&self, master: &MasterPalette) -> RgbImage {
+        self.to_rgb(&master.sub_palette_rgb(self.palette))
+    }
+}
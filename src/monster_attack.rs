@@ -0,0 +1,326 @@
+use crate::character::Character;
+use crate::distribution::{dice_distribution, ValueDistribution};
+use crate::element::{Element, Elements};
+use crate::monster::{Monster, MonsterAbility, MonsterMeleeDiceExpr};
+use crate::rng::gen_range_distribution;
+
+/// モンスターの打撃を受ける側のパラメータ。
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct MeleeDefender {
+    pub ac: i32,
+    pub element_resistance: Elements,
+    /// 追加効果ごとの、効果を免れる確率。
+    pub saves: OnHitSaves,
+}
+
+impl MeleeDefender {
+    /// キャラクターの装備と、追加効果を免れる確率から求める。
+    ///
+    /// 追加効果を免れる確率は原作の判定式が未解析なので、呼び出し側が仮定して与える
+    /// ([`OnHitSaves`] を参照)。
+    pub fn from_character(chara: &Character, saves: OnHitSaves) -> Self {
+        Self {
+            ac: chara.ac(),
+            element_resistance: chara.element_resistance(),
+            saves,
+        }
+    }
+}
+
+/// 命中した打撃の追加効果を、属性抵抗以外の理由で免れる確率 (効果ごと)。
+///
+/// NOTE: 原作の追加効果の判定ルーチンは未解析で、効果ごとのセービングスローの式は分かっていない。
+/// そのため値は呼び出し側が仮定して与える。被害を多めに見積もるなら [`OnHitSaves::NONE`] を使う。
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct OnHitSaves {
+    pub poison: f64,
+    pub paralyze: f64,
+    pub petrify: f64,
+    pub drain: f64,
+    pub critical: f64,
+}
+
+impl OnHitSaves {
+    /// 全て 0 (属性で防げなければ必ず効果を受ける)。
+    pub const NONE: Self = Self {
+        poison: 0.0,
+        paralyze: 0.0,
+        petrify: 0.0,
+        drain: 0.0,
+        critical: 0.0,
+    };
+
+    /// 指定した追加効果を免れる確率を返す。
+    pub fn get(&self, effect: OnHitEffect) -> f64 {
+        match effect {
+            OnHitEffect::Poison => self.poison,
+            OnHitEffect::Paralyze => self.paralyze,
+            OnHitEffect::Petrify => self.petrify,
+            OnHitEffect::Drain(_) => self.drain,
+            OnHitEffect::Critical => self.critical,
+        }
+    }
+}
+
+/// モンスターの打撃の追加効果。
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum OnHitEffect {
+    Poison,
+    Paralyze,
+    Petrify,
+    /// 指定したレベル数のレベルドレイン。
+    Drain(u8),
+    Critical,
+}
+
+impl OnHitEffect {
+    /// 正式名称を返す。
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Poison => "毒",
+            Self::Paralyze => "麻痺",
+            Self::Petrify => "石化",
+            Self::Drain(_) => "レベルドレイン",
+            Self::Critical => "首はね",
+        }
+    }
+
+    /// 対応する抵抗属性を返す (属性で防げない効果なら `None`)。
+    pub fn resist_element(self) -> Option<Element> {
+        match self {
+            Self::Poison => Some(Element::Poison),
+            Self::Petrify => Some(Element::Petrify),
+            Self::Drain(_) => Some(Element::Drain),
+            Self::Paralyze | Self::Critical => None,
+        }
+    }
+
+    /// この効果を受けると以降の打撃の追加効果が意味をなさなくなるかどうかを返す
+    /// (石化または死亡)。
+    pub fn is_incapacitating(self) -> bool {
+        matches!(self, Self::Petrify | Self::Critical)
+    }
+}
+
+/// モンスターの打撃の追加効果を原作での処理順に返す。
+pub fn monster_on_hit_effects(monster: &Monster) -> Vec<OnHitEffect> {
+    let mut effects = Vec::<OnHitEffect>::new();
+
+    if monster.abilitys.contains(MonsterAbility::Poison) {
+        effects.push(OnHitEffect::Poison);
+    }
+    if monster.abilitys.contains(MonsterAbility::Paralyze) {
+        effects.push(OnHitEffect::Paralyze);
+    }
+    if monster.abilitys.contains(MonsterAbility::Petrify) {
+        effects.push(OnHitEffect::Petrify);
+    }
+    if monster.drain_xl > 0 {
+        effects.push(OnHitEffect::Drain(monster.drain_xl));
+    }
+    if monster.abilitys.contains(MonsterAbility::Critical) {
+        effects.push(OnHitEffect::Critical);
+    }
+
+    effects
+}
+
+/// モンスターの打撃 1 回の結果の分布。
+#[derive(Clone, Debug, PartialEq)]
+pub struct MonsterSwingOutcome {
+    pub dice_expr: MonsterMeleeDiceExpr,
+    pub hit_probability: f64,
+    /// 命中した場合のダメージ分布。
+    pub damage_per_hit: ValueDistribution,
+}
+
+/// モンスターの 1 ターン分の打撃をキャラクターが受けた結果の分布。
+#[derive(Clone, Debug, PartialEq)]
+pub struct MonsterAttackOutcome {
+    pub swings: Vec<MonsterSwingOutcome>,
+    /// 1 ターンの合計ダメージ分布。
+    pub damage: ValueDistribution,
+    /// 追加効果ごとの、1 ターンに 1 回以上その効果を受ける確率 (原作での処理順)。
+    pub effects: Vec<(OnHitEffect, f64)>,
+}
+
+/// モンスターの打撃が命中する確率を返す。
+///
+/// モンスターのレベル (HP ダイスの個数) を命中補正値として、
+/// `gen_range(20) + レベル + 対象の AC >= 19` のとき命中するものとする。
+///
+/// NOTE: 原作のモンスターの命中判定ルーチンは未解析で、これはキャラクターの打撃の判定式
+/// ([`crate::melee_hit_probability`]) と、HP ダイスの個数をレベルとみなすことを仮定したもの。
+pub fn monster_hit_probability(monster: &Monster, target_ac: i32) -> f64 {
+    let need = 19 - i32::from(monster.hp_dice_expr.count()) - target_ac;
+
+    gen_range_distribution(20)
+        .into_iter()
+        .zip(0..)
+        .filter(|&(_, roll)| roll >= need)
+        .map(|(p, _)| p)
+        .sum()
+}
+
+/// モンスターの 1 ターン分の打撃をキャラクターに対して行った結果の分布を求める。
+///
+/// 追加効果は命中した打撃ごとに原作での処理順に判定され、対応する属性に抵抗していれば無効、
+/// そうでなければ `defender.saves` の確率で免れ、免れなければ効果を受ける。
+/// 石化または首はねを受けた後の打撃の追加効果は数えない。
+pub fn monster_attack_against(monster: &Monster, defender: &MeleeDefender) -> MonsterAttackOutcome {
+    let hit_probability = monster_hit_probability(monster, defender.ac);
+
+    let swings: Vec<_> = monster
+        .melee_dice_exprs
+        .iter()
+        .map(|&dice_expr| {
+            let damage_per_hit = dice_distribution(
+                dice_expr.count(),
+                dice_expr.face(),
+                i32::from(dice_expr.bias_decoded()),
+                1,
+            );
+            MonsterSwingOutcome {
+                dice_expr,
+                hit_probability,
+                damage_per_hit,
+            }
+        })
+        .collect();
+
+    let damage = swings
+        .iter()
+        .fold(ValueDistribution::constant(0), |acc, swing| {
+            let miss = ValueDistribution::constant(0).scale(1.0 - swing.hit_probability);
+            let dist = miss.merge(&swing.damage_per_hit.scale(swing.hit_probability));
+            acc.convolve(&dist)
+        });
+
+    let effects = monster_on_hit_effects(monster);
+    let land_probs: Vec<f64> = effects
+        .iter()
+        .map(|effect| {
+            let resisted = effect
+                .resist_element()
+                .is_some_and(|elem| defender.element_resistance.contains(elem));
+            if resisted {
+                0.0
+            } else {
+                1.0 - defender.saves.get(*effect)
+            }
+        })
+        .collect();
+
+    let effects = effects
+        .iter()
+        .enumerate()
+        .map(|(i, &effect)| {
+            let p = effect_probability(&swings, &effects, &land_probs, i);
+            (effect, p)
+        })
+        .collect();
+
+    MonsterAttackOutcome {
+        swings,
+        damage,
+        effects,
+    }
+}
+
+/// `idx` 番目の追加効果を 1 ターンに 1 回以上受ける確率を求める。
+///
+/// 状態 (行動不能になっていない, まだ効果を受けていない) の確率を打撃ごとに更新していく。
+fn effect_probability(
+    swings: &[MonsterSwingOutcome],
+    effects: &[OnHitEffect],
+    land_probs: &[f64],
+    idx: usize,
+) -> f64 {
+    // [行動可能かつ未発生, 行動可能かつ発生済み] の確率。行動不能になった後は変化しない。
+    let mut p_active_none = 1.0;
+    let mut p_landed = 0.0;
+
+    for swing in swings {
+        let mut hit_none = p_active_none * swing.hit_probability;
+        let miss_none = p_active_none * (1.0 - swing.hit_probability);
+
+        // 命中した場合、追加効果を順に判定する。`idx` より前の行動不能効果で打ち切られうる。
+        for (i, (&effect, &p)) in effects.iter().zip(land_probs).enumerate() {
+            if i == idx {
+                p_landed += hit_none * p;
+                hit_none *= 1.0 - p;
+                break;
+            }
+            if effect.is_incapacitating() {
+                hit_none *= 1.0 - p;
+            }
+        }
+
+        // `idx` より後の行動不能効果の分を差し引く。
+        for (&effect, &p) in effects.iter().zip(land_probs).skip(idx + 1) {
+            if effect.is_incapacitating() {
+                hit_none *= 1.0 - p;
+            }
+        }
+
+        p_active_none = miss_none + hit_none;
+    }
+
+    p_landed
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::class::Class;
+    use crate::item::ItemKind;
+    use crate::test_util::{dummy_character, dummy_item, dummy_monster};
+
+    use super::*;
+
+    #[test]
+    fn test_monster_attack() {
+        let mut monster = dummy_monster();
+        monster.hp_dice_expr = crate::monster::MonsterHpDiceExpr::new(100, 1, 0);
+        monster.melee_dice_exprs = vec![MonsterMeleeDiceExpr::new(1, 1, 0); 2];
+        monster.abilitys = MonsterAbility::Poison | MonsterAbility::Petrify;
+
+        let defender = MeleeDefender {
+            ac: 10,
+            element_resistance: Element::Poison.into(),
+            saves: OnHitSaves {
+                petrify: 0.25,
+                ..OnHitSaves::NONE
+            },
+        };
+        let outcome = monster_attack_against(&monster, &defender);
+
+        assert_eq!(outcome.damage.probability(2), 1.0);
+        assert_eq!(outcome.effects[0], (OnHitEffect::Poison, 0.0));
+
+        // 1 回目で石化する確率 p と、1 回目で免れ 2 回目で石化する確率 (1-p)p の和。
+        let p = 0.75;
+        let (effect, p_petrify) = outcome.effects[1];
+        assert_eq!(effect, OnHitEffect::Petrify);
+        assert!((p_petrify - (p + (1.0 - p) * p)).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_melee_defender_from_character() {
+        let mut chara = dummy_character(Class::Fighter);
+        let mut robe = dummy_item(ItemKind::Armor);
+        robe.ac = 2;
+        robe.element_resistance = Element::Poison.into();
+        chara.equip(0, robe).unwrap();
+
+        let saves = OnHitSaves {
+            paralyze: 0.5,
+            ..OnHitSaves::NONE
+        };
+        let defender = MeleeDefender::from_character(&chara, saves);
+
+        assert_eq!(defender.ac, chara.ac());
+        assert_eq!(defender.element_resistance, Elements::from(Element::Poison));
+        assert_eq!(defender.saves.get(OnHitEffect::Paralyze), 0.5);
+        assert_eq!(defender.saves.get(OnHitEffect::Poison), 0.0);
+    }
+}
@@ -0,0 +1,132 @@
+use anyhow::Context as _;
+
+use crate::image::Rgb;
+
+/// NES のマスターパレットの色数。
+pub const MASTER_PALETTE_LEN: usize = 64;
+
+/// NES のマスターパレット。
+///
+/// 実機の PPU は RGB 値を持たないので、エミュレータ等で使われる近似値から選択する。
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct MasterPalette([Rgb; MASTER_PALETTE_LEN]);
+
+impl MasterPalette {
+    /// 指定した色インデックスの色を返す (上位 2 ビットは無視される)。
+    pub fn color(&self, idx: u8) -> Rgb {
+        self.0[usize::from(idx & 0x3F)]
+    }
+
+    /// 色インデックス 4 個からなるサブパレットを RGB に変換する。
+    pub fn sub_palette_rgb(&self, sub: [u8; 4]) -> [Rgb; 4] {
+        sub.map(|idx| self.color(idx))
+    }
+}
+
+/// マスターパレットの種類。
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum MasterPaletteKind {
+    /// 多くのツールで使われている 2C02 の近似値。
+    #[default]
+    Basic,
+    /// FCEUX のデフォルトパレット。
+    Fceux,
+}
+
+impl MasterPaletteKind {
+    /// 正式名称を返す。
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Basic => "basic",
+            Self::Fceux => "fceux",
+        }
+    }
+
+    /// マスターパレットを返す。
+    pub fn palette(self) -> MasterPalette {
+        let table = match self {
+            Self::Basic => &MASTER_PALETTE_BASIC,
+            Self::Fceux => &MASTER_PALETTE_FCEUX,
+        };
+
+        MasterPalette(table.map(Rgb::from_u32))
+    }
+
+    /// 全てのマスターパレットの種類を返す。
+    pub fn iter(
+    ) -> impl DoubleEndedIterator<Item = Self> + ExactSizeIterator + std::iter::FusedIterator + Clone
+    {
+        [Self::Basic, Self::Fceux].into_iter()
+    }
+}
+
+impl std::str::FromStr for MasterPaletteKind {
+    type Err = anyhow::Error;
+
+    /// 正式名称 (`name()` の値) から作る。
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::iter()
+            .find(|kind| kind.name() == s)
+            .with_context(|| format!("unknown master palette: '{s}'"))
+    }
+}
+
+#[rustfmt::skip]
+const MASTER_PALETTE_BASIC: [u32; MASTER_PALETTE_LEN] = [
+    0x7C7C7C, 0x0000FC, 0x0000BC, 0x4428BC, 0x940084, 0xA80020, 0xA81000, 0x881400,
+    0x503000, 0x007800, 0x006800, 0x005800, 0x004058, 0x000000, 0x000000, 0x000000,
+    0xBCBCBC, 0x0078F8, 0x0058F8, 0x6844FC, 0xD800CC, 0xE40058, 0xF83800, 0xE45C10,
+    0xAC7C00, 0x00B800, 0x00A800, 0x00A844, 0x008888, 0x000000, 0x000000, 0x000000,
+    0xF8F8F8, 0x3CBCFC, 0x6888FC, 0x9878F8, 0xF878F8, 0xF85898, 0xF87858, 0xFCA044,
+    0xF8B800, 0xB8F818, 0x58D854, 0x58F898, 0x00E8D8, 0x787878, 0x000000, 0x000000,
+    0xFCFCFC, 0xA4E4FC, 0xB8B8F8, 0xD8B8F8, 0xF8B8F8, 0xF8A4C0, 0xF0D0B0, 0xFCE0A8,
+    0xF8D878, 0xD8F878, 0xB8F8B8, 0xB8F8D8, 0x00FCFC, 0xF8D8F8, 0x000000, 0x000000,
+];
+
+#[rustfmt::skip]
+const MASTER_PALETTE_FCEUX: [u32; MASTER_PALETTE_LEN] = [
+    0x747474, 0x24188C, 0x0000A8, 0x44009C, 0x8C0074, 0xA80010, 0xA40000, 0x7C0800,
+    0x402C00, 0x004400, 0x005000, 0x003C14, 0x183C5C, 0x000000, 0x000000, 0x000000,
+    0xBCBCBC, 0x0070EC, 0x2038EC, 0x8000F0, 0xBC00BC, 0xE40058, 0xD82800, 0xC84C0C,
+    0x887000, 0x009400, 0x00A800, 0x009038, 0x008088, 0x000000, 0x000000, 0x000000,
+    0xFCFCFC, 0x3CBCFC, 0x5C94FC, 0xCC88FC, 0xF478FC, 0xFC74B4, 0xFC7460, 0xFC9838,
+    0xF0BC3C, 0x80D010, 0x4CDC48, 0x58F898, 0x00E8D8, 0x787878, 0x000000, 0x000000,
+    0xFCFCFC, 0xA8E4FC, 0xC4D4FC, 0xD4C8FC, 0xFCC4FC, 0xFCC4D8, 0xFCBCB0, 0xFCD8A8,
+    0xFCE4A0, 0xE0FCA0, 0xA8F0BC, 0xB0FCCC, 0x9CFCF0, 0xC4C4C4, 0x000000, 0x000000,
+];
+
+/// 原作のパレット (BG 用、スプライト用それぞれサブパレット 4 個)。
+///
+/// 各要素は NES のマスターパレットにおける色インデックス。
+/// ROM 内のパレットテーブルは未特定なので、エミュレータでダンプした PPU のパレット RAM から作る。
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct GamePalette {
+    pub bg: [[u8; 4]; 4],
+    pub sprite: [[u8; 4]; 4],
+}
+
+impl GamePalette {
+    /// PPU のパレット RAM と同じ並び (32 バイト) から作る。
+    pub fn from_bytes(buf: &[u8; 32]) -> Self {
+        let sub = |i: usize| -> [u8; 4] { buf[4 * i..][..4].try_into().unwrap() };
+
+        Self {
+            bg: std::array::from_fn(sub),
+            sprite: std::array::from_fn(|i| sub(4 + i)),
+        }
+    }
+
+    /// 全サブパレットを RGB に変換し、BG 0..=3, スプライト 0..=3 の順に並べた 32 色を返す。
+    ///
+    /// `IndexedImage::draw_tile()` の `base` に `4 * サブパレット番号` を指定して描画した画像にそのまま適用できる。
+    pub fn to_rgb(&self, master: &MasterPalette) -> [Rgb; 32] {
+        std::array::from_fn(|i| {
+            let sub = if i < 16 {
+                self.bg[i / 4]
+            } else {
+                self.sprite[(i - 16) / 4]
+            };
+            master.color(sub[i % 4])
+        })
+    }
+}
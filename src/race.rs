@@ -0,0 +1,99 @@
+use crate::stat::Stats;
+
+/// 種族。
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub enum Race {
+    Human,
+    Elf,
+    Dwarf,
+    Gnome,
+    Hobbit,
+}
+
+impl Race {
+    /// 種族ID (`0..=4`) から種族を作る。
+    pub fn from_id(id: u8) -> Option<Self> {
+        match id {
+            0 => Some(Self::Human),
+            1 => Some(Self::Elf),
+            2 => Some(Self::Dwarf),
+            3 => Some(Self::Gnome),
+            4 => Some(Self::Hobbit),
+            _ => None,
+        }
+    }
+
+    /// 種族ID (`0..=4`) を返す。
+    pub fn to_id(self) -> u8 {
+        match self {
+            Self::Human => 0,
+            Self::Elf => 1,
+            Self::Dwarf => 2,
+            Self::Gnome => 3,
+            Self::Hobbit => 4,
+        }
+    }
+
+    /// 正式名称を返す。
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Human => "Human",
+            Self::Elf => "Elf",
+            Self::Dwarf => "Dwarf",
+            Self::Gnome => "Gnome",
+            Self::Hobbit => "Hobbit",
+        }
+    }
+
+    /// 日本語での正式名称を返す。
+    pub fn name_ja(self) -> &'static str {
+        match self {
+            Self::Human => "人間",
+            Self::Elf => "エルフ",
+            Self::Dwarf => "ドワーフ",
+            Self::Gnome => "ノーム",
+            Self::Hobbit => "ホビット",
+        }
+    }
+
+    /// キャラクター作成時の能力値の基本値を返す。
+    ///
+    /// NOTE: 取扱説明書などで知られている値であり、ROM から抽出したものではない
+    /// (ROM 内のテーブルの位置が未特定のため)。
+    pub fn base_stats(self) -> Stats {
+        Stats::new(match self {
+            Self::Human => [8, 8, 5, 8, 8, 9],
+            Self::Elf => [7, 10, 10, 6, 9, 6],
+            Self::Dwarf => [10, 7, 10, 10, 5, 6],
+            Self::Gnome => [7, 7, 10, 8, 10, 7],
+            Self::Hobbit => [5, 7, 7, 6, 10, 15],
+        })
+    }
+
+    /// 全ての種族を昇順で返す。
+    pub fn iter(
+    ) -> impl DoubleEndedIterator<Item = Self> + ExactSizeIterator + std::iter::FusedIterator + Clone
+    {
+        [
+            Self::Human,
+            Self::Elf,
+            Self::Dwarf,
+            Self::Gnome,
+            Self::Hobbit,
+        ]
+        .into_iter()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::stat::Stat;
+
+    #[test]
+    fn test_base_stats() {
+        assert_eq!(Race::Human.base_stats().to_array(), [8, 8, 5, 8, 8, 9]);
+        assert_eq!(Race::Hobbit.base_stats().get(Stat::Luck), 15);
+    }
+}
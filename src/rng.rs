@@ -31,6 +31,61 @@ impl GameRng {
     }
 }
 
+/// [`GameRng::gen_range`] の結果の確率分布を返す (内部状態が一様分布に従うと仮定)。
+///
+/// 戻り値の `i` 番目の要素が `i` が出る確率。`end` が 0 の場合、常に 0 が出るものとする。
+pub fn gen_range_distribution(end: u8) -> Vec<f64> {
+    let mut counts = vec![0_u32; usize::from(end.max(1))];
+
+    for r in 0..=u8::MAX {
+        let res = (u16::from(end) * u16::from(r)) >> 8;
+        counts[usize::from(res)] += 1;
+    }
+
+    counts.into_iter().map(|c| f64::from(c) / 256.0).collect()
+}
+
+/// `gen_range(100) < percent` となる確率を返す (内部状態が一様分布に従うと仮定)。
+///
+/// 原作で「`percent` % の確率で」と書かれる判定の実際の確率。
+pub fn percent_probability(percent: u8) -> f64 {
+    gen_range_distribution(100)
+        .iter()
+        .take(usize::from(percent))
+        .sum()
+}
+
+/// 「基準値 + 乱数」で決めた値に、一定確率で刻み幅を加算し続ける処理の結果の分布を返す。
+///
+/// アイテムドロップや徘徊モンスターの決定に使われる。値は `min + (0..range の乱数)` で決まり、
+/// その後 `max_step_count` 回を上限として `step_percent` % の確率で `step` が加算され続ける。
+/// 戻り値は (値, 確率) の列で、値の昇順。
+pub(crate) fn stepped_value_distribution(
+    min: u8,
+    range: u8,
+    step: u8,
+    max_step_count: u8,
+    step_percent: u8,
+) -> Vec<(usize, f64)> {
+    let base = gen_range_distribution(range);
+    let p_step = percent_probability(step_percent);
+
+    let mut probs = std::collections::BTreeMap::<usize, f64>::new();
+    for (i, &p_base) in base.iter().enumerate() {
+        for n in 0..=max_step_count {
+            let p_n = if n == max_step_count {
+                p_step.powi(i32::from(n))
+            } else {
+                p_step.powi(i32::from(n)) * (1.0 - p_step)
+            };
+            let value = usize::from(min) + i + usize::from(step) * usize::from(n);
+            *probs.entry(value).or_default() += p_base * p_n;
+        }
+    }
+
+    probs.into_iter().filter(|&(_, p)| p > 0.0).collect()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -62,4 +117,46 @@ mod tests {
         assert_eq!(rng.gen_range(7), 1);
         assert_eq!(rng.state(), 0x3BDE);
     }
+
+    #[test]
+    fn test_gen_range_distribution() {
+        assert_eq!(gen_range_distribution(0), [1.0]);
+        assert_eq!(gen_range_distribution(2), [0.5, 0.5]);
+
+        let dist = gen_range_distribution(20);
+        assert_eq!(dist.len(), 20);
+        assert_eq!(dist[0], 13.0 / 256.0);
+        assert_eq!(dist[4], 12.0 / 256.0);
+        assert!((dist.iter().sum::<f64>() - 1.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_stepped_value_distribution() {
+        // 加算なし
+        assert_eq!(
+            stepped_value_distribution(3, 2, 10, 0, 50),
+            [(3, 0.5), (4, 0.5)]
+        );
+
+        // 100 % で加算されるなら上限まで加算される
+        assert_eq!(stepped_value_distribution(0, 1, 4, 3, 100), [(12, 1.0)]);
+
+        let dist = stepped_value_distribution(0, 1, 1, 2, 50);
+        let p = percent_probability(50);
+        let expected = [(0, 1.0 - p), (1, p * (1.0 - p)), (2, p * p)];
+        assert_eq!(dist.len(), expected.len());
+        for ((value, prob), (value_expected, prob_expected)) in dist.into_iter().zip(expected) {
+            assert_eq!(value, value_expected);
+            assert!((prob - prob_expected).abs() < 1e-12);
+        }
+        assert!(
+            (stepped_value_distribution(5, 20, 3, 4, 30)
+                .iter()
+                .map(|&(_, p)| p)
+                .sum::<f64>()
+                - 1.0)
+                .abs()
+                < 1e-12
+        );
+    }
 }
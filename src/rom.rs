@@ -6,13 +6,19 @@ use anyhow::{ensure, Context as _};
 ///
 /// headerless SHA-1 hash: 98cbf6d8d410c6725b59c08c35a22f29c3531aa8
 #[derive(Debug)]
-pub struct Rom(Box<[u8; ROM_LEN]>);
+pub struct Rom {
+    header: [u8; INES_HEADER_LEN],
+    body: Box<[u8; ROM_LEN]>,
+}
+
+const INES_HEADER_LEN: usize = 16;
 
 const PRG_BANK_COUNT: usize = 16;
 const PRG_BANK_LEN: usize = 0x2000;
 const PRG_LEN: usize = PRG_BANK_COUNT * PRG_BANK_LEN;
 
-const CHR_BANK_COUNT: usize = 128;
+/// CHR バンク (0x400 バイト単位) の数。
+pub const CHR_BANK_COUNT: usize = 128;
 const CHR_BANK_LEN: usize = 0x400;
 const CHR_LEN: usize = CHR_BANK_COUNT * CHR_BANK_LEN;
 
@@ -36,7 +42,6 @@ impl Rom {
 
     /// iNES 形式のバイト列から ROM をロードする。
     pub fn from_ines_bytes(ines: &[u8]) -> anyhow::Result<Self> {
-        const INES_HEADER_LEN: usize = 16;
         const INES_FILE_LEN: usize = INES_HEADER_LEN + ROM_LEN;
         const INES_MAGIC: &[u8] = b"NES\x1A";
 
@@ -48,10 +53,32 @@ impl Rom {
 
         ensure!(ines.starts_with(INES_MAGIC), "iNES magic not found");
 
-        let (_, rom) = ines.split_first_chunk::<INES_HEADER_LEN>().unwrap();
-        let rom: Box<[u8; ROM_LEN]> = Box::<[u8]>::from(rom).try_into().unwrap();
+        let (&header, rom) = ines.split_first_chunk::<INES_HEADER_LEN>().unwrap();
+        let body: Box<[u8; ROM_LEN]> = Box::<[u8]>::from(rom).try_into().unwrap();
+
+        Ok(Self { header, body })
+    }
+
+    /// iNES 形式のバイト列に変換する (ヘッダはロード時のものがそのまま使われる)。
+    pub fn to_ines_bytes(&self) -> Vec<u8> {
+        let mut ines = Vec::with_capacity(INES_HEADER_LEN + ROM_LEN);
+        ines.extend_from_slice(&self.header);
+        ines.extend_from_slice(self.body.as_slice());
+
+        ines
+    }
+
+    /// iNES 形式のファイルとして保存する。
+    pub fn save_ines_file<P>(&self, path: P) -> anyhow::Result<()>
+    where
+        P: AsRef<Path>,
+    {
+        self._save_ines_file(path.as_ref())
+    }
 
-        Ok(Self(rom))
+    fn _save_ines_file(&self, path: &Path) -> anyhow::Result<()> {
+        std::fs::write(path, self.to_ines_bytes())
+            .with_context(|| format!("cannot write '{}'", path.display()))
     }
 
     /// PRG-ROM 全体を返す。
@@ -68,6 +95,15 @@ impl Rom {
             .unwrap()
     }
 
+    /// 指定したIDの PRG バンク (0x2000 バイト単位) を書き換え可能な形で返す。
+    pub fn prg_bank_mut(&mut self, id: usize) -> &mut [u8; PRG_BANK_LEN] {
+        assert!(id < PRG_BANK_COUNT);
+
+        (&mut self.body[PRG_BANK_LEN * id..][..PRG_BANK_LEN])
+            .try_into()
+            .unwrap()
+    }
+
     /// 固定 PRG バンク (PRG-ROM 末尾の 0x4000 バイト) を返す。
     pub fn prg_fixed(&self) -> &[u8; 2 * PRG_BANK_LEN] {
         self.prg()[PRG_BANK_LEN * 14..][..2 * PRG_BANK_LEN]
@@ -90,7 +126,7 @@ impl Rom {
     }
 
     fn split_prg_chr(&self) -> (&[u8; PRG_LEN], &[u8; CHR_LEN]) {
-        let (prg, chr) = self.0.split_first_chunk::<PRG_LEN>().unwrap();
+        let (prg, chr) = self.body.split_first_chunk::<PRG_LEN>().unwrap();
         let chr: &[u8; CHR_LEN] = chr.try_into().unwrap();
 
         (prg, chr)
@@ -0,0 +1,55 @@
+use crate::class::{Classes, ClassesDisplayInitialPad};
+use crate::stat::Stat;
+
+/// アイテムのスペシャルパワー (SP) の効果。
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum SpecialPower {
+    /// 使用者の能力値を増減する。
+    StatDelta(Stat, i8),
+    /// 使用者の年齢を増減する。
+    AgeDelta(i8),
+    /// 使用者を指定した職業マスクのいずれかに転職させる。
+    ChangeClass(Classes),
+    /// 使用者の経験値を増やす。
+    XpGain(u64),
+    /// 使用者が灰になる。
+    Ash,
+    /// 使用者の最大HPを増減する。
+    MaxHpDelta(i8),
+    /// 生存者全員のHPを全快する。
+    HealParty,
+    /// 使用者が死亡し、指定した職業マスクのいずれかに転職する。
+    DieAndChangeClass(Classes),
+    /// 使用者の全ての現在MPを指定した値にする。
+    SetMp(u8),
+    /// 使用者が全呪文を忘れ、全ての現在MPを指定した値にする。
+    ForgetSpellsAndSetMp(u8),
+    /// 未使用 (効果なし)。引数は SP の ID。
+    Unused(u8),
+}
+
+impl std::fmt::Display for SpecialPower {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match *self {
+            Self::StatDelta(stat, delta) => write!(f, "{} {delta:+}", stat.name()),
+            Self::AgeDelta(delta) => write!(f, "年齢 {delta:+}"),
+            Self::ChangeClass(classes) => write!(
+                f,
+                "{} のいずれかに転職",
+                ClassesDisplayInitialPad::new(classes, '-')
+            ),
+            Self::XpGain(xp) => write!(f, "経験値 +{xp}"),
+            Self::Ash => f.write_str("灰化"),
+            Self::MaxHpDelta(delta) => write!(f, "最大HP {delta:+}"),
+            Self::HealParty => f.write_str("生存者全員のHP全快"),
+            Self::DieAndChangeClass(classes) => write!(
+                f,
+                "死亡し、{} のいずれかに転職",
+                ClassesDisplayInitialPad::new(classes, '-')
+            ),
+            Self::SetMp(mp) => write!(f, "全ての現在MPが {mp} になる"),
+            Self::ForgetSpellsAndSetMp(mp) => write!(f, "全呪文を忘れ、全ての現在MPが {mp} になる"),
+            Self::Unused(id) => write!(f, "(未使用{id})"),
+        }
+    }
+}
@@ -0,0 +1,302 @@
+use num_enum::{IntoPrimitive, TryFromPrimitive};
+
+use crate::dice::define_dice_expr;
+use crate::element::Elements;
+use crate::monster::{MonsterKind, MonsterKinds};
+
+/// 呪文。
+#[repr(u8)]
+#[derive(
+    Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd, IntoPrimitive, TryFromPrimitive,
+)]
+pub enum Spell {
+    Halito = 0,
+    Mogref,
+    Katino,
+    Dumapic,
+    Dilto,
+    Sopic,
+    Melito,
+    Mahalito,
+    Molito,
+    Morlis,
+    Tzalik,
+    Dalto,
+    Lahalito,
+    Mamorlis,
+    Makanito,
+    Madalto,
+    Zilwan,
+    Lakanito,
+    Masopic,
+    Haman,
+    Ladalto,
+    Malor,
+    Mahaman,
+    Tiltowait,
+    Kalki,
+    Dios,
+    Badios,
+    Milwa,
+    Porfic,
+    Matu,
+    Calfo,
+    Manifo,
+    Montino,
+    Kandi,
+    Dial,
+    Badial,
+    Dialko,
+    Latumapic,
+    Lomilwa,
+    Litokan,
+    Bamatu,
+    Latumofis,
+    Maporfic,
+    Dialma,
+    Badialma,
+    Di,
+    Badi,
+    Lorto,
+    Madi,
+    Mabadi,
+    Loktofeit,
+    Malikto,
+    Kadorto,
+}
+
+/// 各系統の呪文レベルごとの呪文数 (呪文IDはこの順に並んでいる)。
+const MAGE_SPELL_COUNTS: [u8; 7] = [4, 3, 2, 4, 3, 4, 4];
+const CLERIC_SPELL_COUNTS: [u8; 7] = [5, 4, 4, 4, 6, 4, 2];
+
+impl Spell {
+    /// 呪文ID (`0..=52`) から呪文を作る。
+    pub fn from_id(id: u8) -> Option<Self> {
+        Self::try_from(id).ok()
+    }
+
+    /// 呪文ID (`0..=52`) を返す。
+    pub fn to_id(self) -> u8 {
+        self.into()
+    }
+
+    /// 正式名称を返す。
+    pub fn name(self) -> &'static str {
+        const TABLE: [&str; 53] = [
+            "Halito",
+            "Mogref",
+            "Katino",
+            "Dumapic",
+            "Dilto",
+            "Sopic",
+            "Melito",
+            "Mahalito",
+            "Molito",
+            "Morlis",
+            "Tzalik",
+            "Dalto",
+            "Lahalito",
+            "Mamorlis",
+            "Makanito",
+            "Madalto",
+            "Zilwan",
+            "Lakanito",
+            "Masopic",
+            "Haman",
+            "Ladalto",
+            "Malor",
+            "Mahaman",
+            "Tiltowait",
+            "Kalki",
+            "Dios",
+            "Badios",
+            "Milwa",
+            "Porfic",
+            "Matu",
+            "Calfo",
+            "Manifo",
+            "Montino",
+            "Kandi",
+            "Dial",
+            "Badial",
+            "Dialko",
+            "Latumapic",
+            "Lomilwa",
+            "Litokan",
+            "Bamatu",
+            "Latumofis",
+            "Maporfic",
+            "Dialma",
+            "Badialma",
+            "Di",
+            "Badi",
+            "Lorto",
+            "Madi",
+            "Mabadi",
+            "Loktofeit",
+            "Malikto",
+            "Kadorto",
+        ];
+
+        TABLE[usize::from(self.to_id())]
+    }
+
+    /// 系統を返す。
+    pub fn school(self) -> SpellSchool {
+        let mage_count: u8 = MAGE_SPELL_COUNTS.iter().sum();
+
+        if self.to_id() < mage_count {
+            SpellSchool::Mage
+        } else {
+            SpellSchool::Cleric
+        }
+    }
+
+    /// 呪文レベル (`1..=7`) を返す。
+    pub fn level(self) -> u8 {
+        let (counts, first) = match self.school() {
+            SpellSchool::Mage => (MAGE_SPELL_COUNTS, 0),
+            SpellSchool::Cleric => (CLERIC_SPELL_COUNTS, MAGE_SPELL_COUNTS.iter().sum()),
+        };
+
+        let mut idx = self.to_id() - first;
+        for (lv, &count) in (1..).zip(&counts) {
+            if idx < count {
+                return lv;
+            }
+            idx -= count;
+        }
+
+        unreachable!()
+    }
+
+    /// 呪文が効くモンスター種別を返す (種別で限定されないなら `None`)。
+    ///
+    /// NOTE: ROM の呪文データからは読み取れない (呪文固有の処理ルーチンが未解析) ので、
+    /// 原作のマニュアルの呪文説明に基づく。
+    pub fn affected_monster_kinds(self) -> Option<MonsterKinds> {
+        match self {
+            Self::Zilwan => Some(MonsterKind::Undead.into()),
+            _ => None,
+        }
+    }
+
+    /// 指定した系統、レベルの呪文を昇順で返す。
+    pub fn iter_by_level(school: SpellSchool, level: u8) -> impl Iterator<Item = Self> + Clone {
+        Self::iter().filter(move |spell| spell.school() == school && spell.level() == level)
+    }
+
+    /// 全ての呪文を昇順で返す。
+    pub fn iter(
+    ) -> impl DoubleEndedIterator<Item = Self> + ExactSizeIterator + std::iter::FusedIterator + Clone
+    {
+        (0..=Self::Kadorto.to_id()).map(|id| Self::from_id(id).unwrap())
+    }
+}
+
+/// 呪文の系統。
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub enum SpellSchool {
+    Mage,
+    Cleric,
+}
+
+impl SpellSchool {
+    /// 正式名称を返す。
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Mage => "魔術師",
+            Self::Cleric => "僧侶",
+        }
+    }
+
+    /// 全ての系統を返す。
+    pub fn iter(
+    ) -> impl DoubleEndedIterator<Item = Self> + ExactSizeIterator + std::iter::FusedIterator + Clone
+    {
+        [Self::Mage, Self::Cleric].into_iter()
+    }
+}
+
+/// 呪文の動作パラメータ。
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct SpellData {
+    pub target: SpellTarget,
+    pub usable_in_camp: bool,
+    pub usable_in_battle: bool,
+    /// ダメージ (回復呪文の場合は回復量) のダイス式。
+    pub dice_expr: Option<SpellDiceExpr>,
+    pub elements: Elements,
+}
+
+/// 呪文の対象。
+#[repr(u8)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq, IntoPrimitive, TryFromPrimitive)]
+pub enum SpellTarget {
+    /// 対象なし (術者自身、迷宮など)。
+    None = 0,
+    /// 味方 1 人。
+    Ally = 1,
+    /// 味方全員。
+    Party = 2,
+    /// 敵 1 体。
+    Single = 3,
+    /// 敵 1 グループ。
+    Group = 4,
+    /// 敵全体。
+    All = 5,
+}
+
+impl SpellTarget {
+    /// 正式名称を返す。
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::None => "なし",
+            Self::Ally => "味方1人",
+            Self::Party => "味方全員",
+            Self::Single => "敵1体",
+            Self::Group => "敵1グループ",
+            Self::All => "敵全体",
+        }
+    }
+}
+
+// 呪文のダメージダイス式。
+define_dice_expr!(SpellDiceExpr);
+
+impl std::fmt::Display for SpellDiceExpr {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let count = self.count;
+        let face = self.face;
+        let bias = self.bias;
+
+        if bias == 0 {
+            write!(f, "{count}d{face}")
+        } else {
+            write!(f, "{count}d{face}+{bias}")
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_spell_school_level() {
+        assert_eq!(Spell::iter().len(), 53);
+
+        assert_eq!(Spell::Halito.school(), SpellSchool::Mage);
+        assert_eq!(Spell::Halito.level(), 1);
+        assert_eq!(Spell::Melito.level(), 2);
+        assert_eq!(Spell::Tiltowait.level(), 7);
+        assert_eq!(Spell::Kalki.school(), SpellSchool::Cleric);
+        assert_eq!(Spell::Kalki.level(), 1);
+        assert_eq!(Spell::Kadorto.level(), 7);
+
+        assert_eq!(
+            Spell::iter_by_level(SpellSchool::Cleric, 7).collect::<Vec<_>>(),
+            [Spell::Malikto, Spell::Kadorto]
+        );
+    }
+}
@@ -0,0 +1,268 @@
+use crate::distribution::{dice_distribution, ValueDistribution};
+use crate::element::Resistance;
+use crate::monster::{Monster, MonsterAbility};
+use crate::spell::{Spell, SpellData, SpellDiceExpr, SpellTarget};
+
+/// 敵に対する呪文の効果。
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum SpellEffect {
+    /// ダメージを与える。
+    Damage(SpellDiceExpr),
+    /// 眠らせる。
+    Sleep,
+    /// 即死させる。
+    InstantDeath,
+}
+
+/// 呪文の敵に対する効果を返す (敵を対象としない呪文、ダメージ・睡眠・即死以外の呪文なら `None`)。
+///
+/// ダメージ呪文かどうかは ROM の呪文データ (対象とダイス式) から判定する。
+/// 呪文固有の処理ルーチンは未解析なので、KATINO (睡眠) と即死呪文 (MAKANITO, LAKANITO, BADI) は
+/// 原作のマニュアルの呪文説明に基づき呪文で判定している。
+pub fn spell_effect(spell: Spell, data: &SpellData) -> Option<SpellEffect> {
+    if !matches!(
+        data.target,
+        SpellTarget::Single | SpellTarget::Group | SpellTarget::All
+    ) {
+        return None;
+    }
+
+    match spell {
+        Spell::Katino => Some(SpellEffect::Sleep),
+        Spell::Makanito | Spell::Lakanito | Spell::Badi => Some(SpellEffect::InstantDeath),
+        _ => data.dice_expr.map(SpellEffect::Damage),
+    }
+}
+
+/// 呪文の判定のうち、原作の処理ルーチンが未解析で仮定に頼る値。
+///
+/// NOTE: 睡眠・即死の判定式は分かっていないので、呪文が無効化されなかった場合に効く確率を
+/// 呼び出し側が仮定して与える。睡眠弱点 ([`MonsterAbility::Sleepy`]) を持つモンスターは
+/// 睡眠・即死の両方に弱いものとし、それぞれ別の確率を与える。
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct SpellAssumptions {
+    /// 睡眠弱点を持たないモンスターが KATINO で眠る確率。
+    pub sleep_probability: f64,
+    /// 睡眠弱点を持つモンスターが KATINO で眠る確率。
+    pub sleep_probability_sleepy: f64,
+    /// 睡眠弱点を持たないモンスターが即死呪文で死ぬ確率。
+    pub death_probability: f64,
+    /// 睡眠弱点を持つモンスターが即死呪文で死ぬ確率。
+    pub death_probability_sleepy: f64,
+}
+
+/// 呪文をモンスター 1 グループに唱えた結果の分布。
+#[derive(Clone, Debug, PartialEq)]
+pub struct SpellOutcome {
+    pub effect: SpellEffect,
+    /// 対象 1 体あたりの、呪文が無効化されない確率。
+    pub affect_probability: f64,
+    /// 対象 1 体あたりの、倒す (睡眠呪文の場合は眠らせる) 確率。
+    pub kill_probability: f64,
+    /// 倒す (睡眠呪文の場合は眠らせる) モンスターの数の分布。
+    pub kill_count: ValueDistribution,
+}
+
+/// 呪文をモンスター 1 グループに唱えた結果の分布を求める。
+///
+/// `group_size` はグループ内のモンスターの数の分布。敵 1 体を対象とする呪文はそのうち 1 体のみに効く。
+/// 各モンスターは無傷 (HP はダイス式に従う) とし、次の順に判定する:
+///
+/// * `spell_resistance` / 256 の確率で呪文が無効化される。
+/// * 呪文の属性に抵抗していれば無効化される。
+/// * 効くモンスター種別が限定されている呪文 ([`Spell::affected_monster_kinds`]) は、
+///   それ以外の種別には効かない。
+/// * 睡眠・即死呪文は `assumptions` の確率で効く (睡眠弱点の有無で確率が変わる)。
+/// * ダメージ呪文はモンスターごとにダメージを振り、HP 以上なら倒す。
+///
+/// 敵に効果のない呪文なら `None` を返す。
+pub fn cast_spell_against_group(
+    spell: Spell,
+    data: &SpellData,
+    monster: &Monster,
+    group_size: &ValueDistribution,
+    assumptions: &SpellAssumptions,
+) -> Option<SpellOutcome> {
+    let effect = spell_effect(spell, data)?;
+
+    let p_not_resisted = 1.0 - f64::from(monster.spell_resistance) / 256.0;
+    let affected_kind = spell
+        .affected_monster_kinds()
+        .is_none_or(|kinds| !(kinds & monster.kinds).is_empty());
+    let affect_probability =
+        match Resistance::for_monster(data.elements, monster.element_resistance) {
+            Resistance::Negated => 0.0,
+            _ if !affected_kind => 0.0,
+            _ => p_not_resisted,
+        };
+
+    let sleepy = monster.abilitys.contains(MonsterAbility::Sleepy);
+    let p_effect = match effect {
+        SpellEffect::Sleep if sleepy => assumptions.sleep_probability_sleepy,
+        SpellEffect::Sleep => assumptions.sleep_probability,
+        SpellEffect::InstantDeath if sleepy => assumptions.death_probability_sleepy,
+        SpellEffect::InstantDeath => assumptions.death_probability,
+        SpellEffect::Damage(dice) => damage_kill_probability(dice, monster),
+    };
+    let kill_probability = affect_probability * p_effect;
+
+    let target_count = |size: usize| {
+        if data.target == SpellTarget::Single {
+            size.min(1)
+        } else {
+            size
+        }
+    };
+    let kill_count = group_size
+        .probs()
+        .iter()
+        .enumerate()
+        .map(|(size, &p)| binomial_distribution(target_count(size), kill_probability).scale(p))
+        .fold(ValueDistribution::from_probs(vec![]), |acc, dist| {
+            acc.merge(&dist)
+        });
+
+    Some(SpellOutcome {
+        effect,
+        affect_probability,
+        kill_probability,
+        kill_count,
+    })
+}
+
+/// ダメージ呪文が無傷のモンスター 1 体を倒す確率を返す (無効化されなかった場合)。
+fn damage_kill_probability(dice: SpellDiceExpr, monster: &Monster) -> f64 {
+    let damage = dice_distribution(dice.count(), dice.face(), i32::from(dice.bias()), 0);
+    let hp = monster.hp_dice_expr;
+    let hp = dice_distribution(hp.count(), hp.face(), i32::from(hp.bias_decoded()), 1);
+
+    hp.probs()
+        .iter()
+        .enumerate()
+        .map(|(hp, p)| p * damage.probability_at_least(hp))
+        .sum()
+}
+
+/// モンスターの出現数の分布を返す。
+pub fn monster_group_size_distribution(monster: &Monster) -> ValueDistribution {
+    let spawn = monster.spawn_dice_expr;
+
+    dice_distribution(
+        spawn.count(),
+        spawn.face(),
+        i32::from(spawn.bias_decoded()),
+        1,
+    )
+}
+
+/// 成功確率 `p` の試行を `n` 回行ったときの成功回数の分布を返す。
+fn binomial_distribution(n: usize, p: f64) -> ValueDistribution {
+    let trial = ValueDistribution::from_probs(vec![1.0 - p, p]);
+
+    trial.convolve_pow(u32::try_from(n).unwrap())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::element::{Element, Elements};
+    use crate::monster::{MonsterAbilitys, MonsterKind};
+    use crate::test_util::dummy_monster;
+
+    use super::*;
+
+    fn spell_data(target: SpellTarget, dice_expr: Option<SpellDiceExpr>) -> SpellData {
+        SpellData {
+            target,
+            usable_in_camp: false,
+            usable_in_battle: true,
+            dice_expr,
+            elements: Element::Fire.into(),
+        }
+    }
+
+    #[test]
+    fn test_cast_spell_against_group() {
+        let mut monster = dummy_monster();
+        monster.hp_dice_expr = crate::monster::MonsterHpDiceExpr::new(1, 1, 0);
+        monster.spell_resistance = 64;
+        let assumptions = SpellAssumptions {
+            sleep_probability: 0.5,
+            sleep_probability_sleepy: 1.0,
+            death_probability: 0.25,
+            death_probability_sleepy: 0.5,
+        };
+
+        let data = spell_data(SpellTarget::Group, Some(SpellDiceExpr::new(1, 1, 0)));
+        let group = ValueDistribution::constant(2);
+        let outcome =
+            cast_spell_against_group(Spell::Mahalito, &data, &monster, &group, &assumptions)
+                .unwrap();
+        assert_eq!(outcome.kill_probability, 0.75);
+        assert!((outcome.kill_count.probability(2) - 0.75 * 0.75).abs() < 1e-9);
+
+        let single = spell_data(SpellTarget::Single, Some(SpellDiceExpr::new(1, 1, 0)));
+        let outcome =
+            cast_spell_against_group(Spell::Halito, &single, &monster, &group, &assumptions)
+                .unwrap();
+        assert_eq!(outcome.kill_count.max_value(), 1);
+
+        monster.element_resistance = Elements::from(Element::Fire);
+        let outcome =
+            cast_spell_against_group(Spell::Mahalito, &data, &monster, &group, &assumptions)
+                .unwrap();
+        assert_eq!(outcome.kill_probability, 0.0);
+
+        let sleep = SpellData {
+            elements: Elements::default(),
+            ..spell_data(SpellTarget::Group, None)
+        };
+        let katino = |monster: &Monster| {
+            cast_spell_against_group(Spell::Katino, &sleep, monster, &group, &assumptions).unwrap()
+        };
+        // 睡眠弱点が無くても一定確率で眠り、睡眠弱点があれば眠りやすくなる。
+        assert_eq!(katino(&monster).kill_probability, 0.75 * 0.5);
+        monster.abilitys = MonsterAbility::Sleepy.into();
+        assert_eq!(katino(&monster).kill_probability, 0.75);
+
+        // 即死呪文も睡眠弱点があれば効きやすくなる
+        let death = SpellData {
+            elements: Elements::default(),
+            ..spell_data(SpellTarget::Group, None)
+        };
+        let lakanito = |monster: &Monster| {
+            cast_spell_against_group(Spell::Lakanito, &death, monster, &group, &assumptions)
+                .unwrap()
+        };
+        assert_eq!(lakanito(&monster).effect, SpellEffect::InstantDeath);
+        assert_eq!(lakanito(&monster).kill_probability, 0.75 * 0.5);
+        monster.abilitys = MonsterAbilitys::default();
+        assert_eq!(lakanito(&monster).kill_probability, 0.75 * 0.25);
+    }
+
+    #[test]
+    fn test_cast_spell_affected_monster_kinds() {
+        let mut monster = dummy_monster();
+        monster.hp_dice_expr = crate::monster::MonsterHpDiceExpr::new(1, 1, 0);
+        let assumptions = SpellAssumptions {
+            sleep_probability: 0.0,
+            sleep_probability_sleepy: 0.0,
+            death_probability: 0.0,
+            death_probability_sleepy: 0.0,
+        };
+        let data = SpellData {
+            elements: Elements::default(),
+            ..spell_data(SpellTarget::Single, Some(SpellDiceExpr::new(1, 1, 0)))
+        };
+        let group = ValueDistribution::constant(1);
+        let zilwan = |monster: &Monster| {
+            cast_spell_against_group(Spell::Zilwan, &data, monster, &group, &assumptions)
+                .unwrap()
+                .kill_probability
+        };
+
+        // ZILWAN は不死のモンスターにのみ効く
+        assert_eq!(zilwan(&monster), 0.0);
+        monster.kinds = MonsterKind::Undead.into();
+        assert_eq!(zilwan(&monster), 1.0);
+    }
+}
@@ -0,0 +1,174 @@
+use anyhow::Context as _;
+
+use crate::spell::{Spell, SpellSchool};
+
+/// 呪文レベルの数。
+pub const SPELL_LEVEL_COUNT: usize = 7;
+
+/// 1 つの呪文レベルあたりの MP の上限。
+pub const SPELL_MP_MAX: u8 = 9;
+
+/// 1 つの系統における、キャラクターレベルに応じた呪文習得の進み方。
+///
+/// 呪文レベル `n` (`1..=7`) の MP は `キャラクターレベル - offset - interval * (n - 1)` で、
+/// これが正のときその呪文レベルを習得可能となる (MP は `SPELL_MP_MAX` で頭打ち)。
+///
+/// NOTE: この式は ROM 内のルーチンから確認したものではなく、仮定したもの (未検証)。
+/// ROM 内の職業ごとのテーブルの位置も未特定なので、`offset` と `interval` は利用者が与える。
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct SpellProgression {
+    /// 呪文レベル 1 を習得するキャラクターレベルから 1 を引いた値。
+    pub offset: u8,
+    /// 次の呪文レベルを習得するまでに必要なキャラクターレベル数。
+    pub interval: u8,
+}
+
+impl SpellProgression {
+    /// 指定したキャラクターレベルでの呪文レベルごとの MP を返す。
+    pub fn mp(self, char_level: u32) -> [u8; SPELL_LEVEL_COUNT] {
+        let mut mp = [0; SPELL_LEVEL_COUNT];
+
+        let mut remain = i64::from(char_level) - i64::from(self.offset);
+        for x in &mut mp {
+            if remain <= 0 {
+                break;
+            }
+            *x = u8::try_from(remain.min(i64::from(SPELL_MP_MAX))).unwrap();
+            remain -= i64::from(self.interval.max(1));
+        }
+
+        mp
+    }
+
+    /// 指定したキャラクターレベルで習得可能な最大の呪文レベルを返す (習得不能なら 0)。
+    pub fn max_spell_level(self, char_level: u32) -> u8 {
+        self.mp(char_level)
+            .iter()
+            .take_while(|&&x| x > 0)
+            .count()
+            .try_into()
+            .unwrap()
+    }
+
+    /// 指定した呪文レベルを習得するのに必要な最小のキャラクターレベルを返す。
+    ///
+    /// `spell_level` は `1..=7` でなければならない。
+    pub fn char_level_for_spell_level(self, spell_level: u8) -> u32 {
+        assert!((1..=7).contains(&spell_level));
+
+        u32::from(self.offset) + u32::from(self.interval.max(1)) * u32::from(spell_level - 1) + 1
+    }
+}
+
+impl std::str::FromStr for SpellProgression {
+    type Err = anyhow::Error;
+
+    /// `offset,interval` の形式 (例: `0,2`) から作る。
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (offset, interval) = s
+            .split_once(',')
+            .context("spell progression must be 'offset,interval'")?;
+        let offset: u8 = offset.trim().parse().context("invalid offset")?;
+        let interval: u8 = interval.trim().parse().context("invalid interval")?;
+
+        Ok(Self { offset, interval })
+    }
+}
+
+/// 職業ごとの呪文習得の進み方。
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub struct ClassSpellProgression {
+    /// 魔術師系呪文の進み方 (習得しないなら `None`)。
+    pub mage: Option<SpellProgression>,
+    /// 僧侶系呪文の進み方 (習得しないなら `None`)。
+    pub cleric: Option<SpellProgression>,
+}
+
+impl ClassSpellProgression {
+    /// 指定した系統の進み方を返す。
+    pub fn progression(&self, school: SpellSchool) -> Option<SpellProgression> {
+        match school {
+            SpellSchool::Mage => self.mage,
+            SpellSchool::Cleric => self.cleric,
+        }
+    }
+
+    /// 指定した系統、キャラクターレベルでの呪文レベルごとの MP を返す。
+    pub fn mp(&self, school: SpellSchool, char_level: u32) -> [u8; SPELL_LEVEL_COUNT] {
+        self.progression(school)
+            .map_or([0; SPELL_LEVEL_COUNT], |prog| prog.mp(char_level))
+    }
+
+    /// `mp` と同様だが、既に覚えている呪文の数を下限とする。
+    ///
+    /// 原作では、転職などで本来の MP より多くの呪文を覚えている場合、
+    /// その呪文レベルの MP は覚えている呪文の数まで引き上げられる。
+    pub fn mp_with_known_spells(
+        &self,
+        school: SpellSchool,
+        char_level: u32,
+        known_spells: &[Spell],
+    ) -> [u8; SPELL_LEVEL_COUNT] {
+        let mut mp = self.mp(school, char_level);
+
+        for (lv, x) in (1..).zip(&mut mp) {
+            let known = known_spells
+                .iter()
+                .filter(|spell| spell.school() == school && spell.level() == lv)
+                .count();
+            *x = (*x).max(u8::try_from(known).unwrap());
+        }
+
+        mp
+    }
+
+    /// 指定したキャラクターレベルで習得可能な全ての呪文を昇順で返す。
+    pub fn learnable_spells(&self, char_level: u32) -> impl Iterator<Item = Spell> + '_ {
+        Spell::iter().filter(move |spell| {
+            self.progression(spell.school())
+                .is_some_and(|prog| spell.level() <= prog.max_spell_level(char_level))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_spell_progression() {
+        let prog = SpellProgression {
+            offset: 0,
+            interval: 2,
+        };
+
+        assert_eq!(prog.mp(1), [1, 0, 0, 0, 0, 0, 0]);
+        assert_eq!(prog.mp(3), [3, 1, 0, 0, 0, 0, 0]);
+        assert_eq!(prog.mp(13), [9, 9, 9, 7, 5, 3, 1]);
+        assert_eq!(prog.max_spell_level(12), 6);
+        assert_eq!(prog.char_level_for_spell_level(7), 13);
+
+        let delayed = SpellProgression {
+            offset: 3,
+            interval: 3,
+        };
+
+        assert_eq!(delayed.max_spell_level(3), 0);
+        assert_eq!(delayed.mp(4), [1, 0, 0, 0, 0, 0, 0]);
+        assert_eq!(delayed.char_level_for_spell_level(2), 7);
+    }
+
+    #[test]
+    fn test_spell_progression_from_str() {
+        assert_eq!(
+            "3,2".parse::<SpellProgression>().unwrap(),
+            SpellProgression {
+                offset: 3,
+                interval: 2,
+            }
+        );
+
+        assert!("3".parse::<SpellProgression>().is_err());
+        assert!("3,x".parse::<SpellProgression>().is_err());
+    }
+}
@@ -0,0 +1,106 @@
+/// 能力値の種別。
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub enum Stat {
+    Strength,
+    Iq,
+    Piety,
+    Vitality,
+    Agility,
+    Luck,
+}
+
+impl Stat {
+    /// 能力値ID (`0..=5`) から能力値の種別を作る。
+    pub fn from_id(id: u8) -> Option<Self> {
+        match id {
+            0 => Some(Self::Strength),
+            1 => Some(Self::Iq),
+            2 => Some(Self::Piety),
+            3 => Some(Self::Vitality),
+            4 => Some(Self::Agility),
+            5 => Some(Self::Luck),
+            _ => None,
+        }
+    }
+
+    /// 能力値ID (`0..=5`) を返す。
+    pub fn to_id(self) -> u8 {
+        match self {
+            Self::Strength => 0,
+            Self::Iq => 1,
+            Self::Piety => 2,
+            Self::Vitality => 3,
+            Self::Agility => 4,
+            Self::Luck => 5,
+        }
+    }
+
+    /// 正式名称を返す。
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Strength => "力",
+            Self::Iq => "知恵",
+            Self::Piety => "信仰心",
+            Self::Vitality => "生命力",
+            Self::Agility => "素早さ",
+            Self::Luck => "運",
+        }
+    }
+
+    /// 全ての能力値の種別を昇順で返す。
+    pub fn iter(
+    ) -> impl DoubleEndedIterator<Item = Self> + ExactSizeIterator + std::iter::FusedIterator + Clone
+    {
+        [
+            Self::Strength,
+            Self::Iq,
+            Self::Piety,
+            Self::Vitality,
+            Self::Agility,
+            Self::Luck,
+        ]
+        .into_iter()
+    }
+}
+
+/// 能力値 6 種の組。
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub struct Stats([u8; 6]);
+
+impl Stats {
+    /// 能力値IDの順に並べた配列から作る。
+    pub fn new(values: [u8; 6]) -> Self {
+        Self(values)
+    }
+
+    /// 能力値IDの順に並べた配列を返す。
+    pub fn to_array(self) -> [u8; 6] {
+        self.0
+    }
+
+    pub fn get(self, stat: Stat) -> u8 {
+        self.0[usize::from(stat.to_id())]
+    }
+
+    pub fn set(&mut self, stat: Stat, value: u8) {
+        self.0[usize::from(stat.to_id())] = value;
+    }
+
+    /// 全ての能力値が `other` の対応する値以上かどうかを返す。
+    pub fn all_ge(self, other: Self) -> bool {
+        self.0.iter().zip(other.0).all(|(&a, b)| a >= b)
+    }
+}
+
+impl std::fmt::Display for Stats {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (i, stat) in Stat::iter().enumerate() {
+            if i > 0 {
+                f.write_str(" ")?;
+            }
+            write!(f, "{}{}", stat.name(), self.get(stat))?;
+        }
+
+        Ok(())
+    }
+}
@@ -21,6 +21,27 @@ impl GameString {
         Ok(Self(inner))
     }
 
+    /// 文字列を Unicode 文字から作る。[`GameChar::from_char`] で変換できない文字を含む場合、エラーを返す。
+    pub fn from_text(text: &str) -> anyhow::Result<Self> {
+        let inner = text
+            .chars()
+            .map(|c| {
+                GameChar::from_char(c).ok_or_else(|| anyhow::anyhow!("unsupported char: {c:?}"))
+            })
+            .collect::<anyhow::Result<Vec<_>>>()?;
+
+        Ok(Self(inner))
+    }
+
+    /// 原作でのバイト列に変換する (終端は含まない)。
+    pub fn to_bytes(&self) -> Vec<u8> {
+        self.0.iter().map(|&ch| u8::from(ch)).collect()
+    }
+
+    pub fn chars(&self) -> &[GameChar] {
+        &self.0
+    }
+
     pub fn len(&self) -> usize {
         self.0.len()
     }
@@ -165,6 +186,13 @@ pub enum GameChar {
 }
 
 impl GameChar {
+    /// Unicode 文字を原作の文字に変換する ([`GameChar::to_char`] の逆変換)。
+    pub fn from_char(c: char) -> Option<Self> {
+        (0..=u8::MAX)
+            .filter_map(|b| Self::try_from(b).ok())
+            .find(|ch| ch.to_char() == c)
+    }
+
     /// 原作の文字を Unicode 文字に変換する。
     ///
     /// 一部の文字は直接対応する Unicode 文字を持たないので、適当にそれっぽく置き換える。
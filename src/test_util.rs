@@ -0,0 +1,107 @@
+//! テスト用のユーティリティ。
+
+use flagset::FlagSet;
+
+use crate::alignment::Alignment;
+use crate::character::{Character, CharacterStatus};
+use crate::class::Class;
+use crate::element::Elements;
+use crate::item::{Item, ItemKind, ItemMeleeDiceExpr};
+use crate::monster::{
+    Monster, MonsterAbilitys, MonsterHpDiceExpr, MonsterKinds, MonsterSpawnDiceExpr,
+};
+use crate::race::Race;
+use crate::rom::Rom;
+use crate::stat::Stats;
+use crate::string::GameString;
+
+/// 環境変数 `WIZARDRY_KOD_ROM` で指定した原作の iNES ROM を読み込む。
+///
+/// ROM はリポジトリに含められないので、未指定なら `None` を返す。ROM を使うテストには
+/// `#[ignore]` を付けておき、ROM を用意した上で `cargo test -- --ignored` で実行する。
+pub(crate) fn original_rom() -> Option<Rom> {
+    let path = std::env::var_os("WIZARDRY_KOD_ROM")?;
+
+    Some(Rom::from_ines_file(path).unwrap())
+}
+
+/// ヘッダ以外の全バイトが 0 の iNES 形式のバイト列を作る。合成した ROM を使うテスト用。
+pub(crate) fn blank_ines() -> Vec<u8> {
+    let mut ines = vec![0; 16 + 0x20000 + 0x20000];
+    ines[..4].copy_from_slice(b"NES\x1A");
+
+    ines
+}
+
+/// 指定した職業で装備なしのキャラクターを作る。
+pub(crate) fn dummy_character(class: Class) -> Character {
+    Character {
+        race: Race::Human,
+        class,
+        alignment: Alignment::Good,
+        stats: Stats::new([18, 10, 10, 10, 10, 10]),
+        level: 10,
+        hp: 50,
+        max_hp: 50,
+        age: 20,
+        status: CharacterStatus::Ok,
+        poisoned: false,
+        equipment: vec![],
+    }
+}
+
+/// 指定した種別の何の効果もないアイテムを作る。
+pub(crate) fn dummy_item(kind: ItemKind) -> Item {
+    Item {
+        name_known: GameString::new(),
+        name_unknown: GameString::new(),
+        kind,
+        alignment: None,
+        cursed: false,
+        special_power_id: 0,
+        break_probability: 0,
+        break_item_id: 0,
+        price: 0,
+        use_spell_id: 0,
+        usable_in_camp: false,
+        usable_in_battle: false,
+        equip_classes: FlagSet::full(),
+        healing: 0,
+        repel_monster_kinds: FlagSet::default(),
+        element_resistance: FlagSet::default(),
+        ac: 0,
+        melee_accuracy: 0,
+        melee_dice_expr: ItemMeleeDiceExpr::new(0, 0, 0),
+        extra_melee_count: 0,
+        critical: false,
+        slay_monster_kinds: FlagSet::default(),
+    }
+}
+
+/// 何の能力も持たないモンスターを作る。
+pub(crate) fn dummy_monster() -> Monster {
+    Monster {
+        name_known_singular: GameString::new(),
+        name_known_plural: GameString::new(),
+        name_unknown_singular: GameString::new(),
+        name_unknown_plural: GameString::new(),
+        kinds: MonsterKinds::default(),
+        spawn_dice_expr: MonsterSpawnDiceExpr::new(1, 1, 0),
+        hp_dice_expr: MonsterHpDiceExpr::new(1, 8, 0),
+        ac: 10,
+        drain_xl: 0,
+        healing: 0,
+        drop_table_id_wandering: 0,
+        drop_table_id_guardian: 0,
+        follower_monster_id: 0,
+        follower_probability: 0,
+        mage_spell_lv: 0,
+        cleric_spell_lv: 0,
+        breath_elements: Elements::default(),
+        spell_resistance: 0,
+        element_resistance: Elements::default(),
+        abilitys: MonsterAbilitys::default(),
+        xp: 0,
+        melee_dice_exprs: vec![],
+    }
+}
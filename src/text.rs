@@ -0,0 +1,377 @@
+use std::io::{BufRead, Write};
+
+use anyhow::{bail, ensure, Context as _};
+
+use crate::string::GameString;
+
+const PRG_BANK_COUNT: usize = 16;
+
+/// ROM 内の位置 (PRG バンクとバンク内オフセット)。
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct PrgLocation {
+    pub bank: usize,
+    pub offset: usize,
+}
+
+impl PrgLocation {
+    pub fn new(bank: usize, offset: usize) -> Self {
+        assert!(bank < PRG_BANK_COUNT);
+
+        Self { bank, offset }
+    }
+
+    /// CPU から見たアドレスを返す。
+    pub fn address(self) -> u16 {
+        let base = prg_bank_base(self.bank);
+
+        u16::try_from(usize::from(base) + self.offset).unwrap()
+    }
+}
+
+impl std::fmt::Display for PrgLocation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:02}:{:04X}", self.bank, self.address())
+    }
+}
+
+impl std::str::FromStr for PrgLocation {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (bank, address) = s.split_once(':').context("location must be 'BB:AAAA'")?;
+        let bank: usize = bank.parse().context("invalid bank")?;
+        ensure!(
+            bank < PRG_BANK_COUNT,
+            "bank must be in 0..{PRG_BANK_COUNT}, got {bank}"
+        );
+        let address = u16::from_str_radix(address, 16).context("invalid address")?;
+
+        let base = prg_bank_base(bank);
+        let offset = usize::from(address.wrapping_sub(base));
+        ensure!(
+            address >= base && offset < 0x2000,
+            "address 0x{address:04X} is out of bank {bank}"
+        );
+
+        Ok(Self::new(bank, offset))
+    }
+}
+
+/// PRG バンクが CPU 空間にマップされるアドレスを返す。
+///
+/// 切り替えバンクは 0x8000 に、固定バンク (14, 15) は 0xC000, 0xE000 にマップされる。
+pub fn prg_bank_base(bank: usize) -> u16 {
+    match bank {
+        14 => 0xC000,
+        15 => 0xE000,
+        _ => 0x8000,
+    }
+}
+
+/// テキストの分類。
+///
+/// アイテム名、モンスター名、イベントメッセージは既知のテーブルから辿る。
+/// 会話、メニュー、商店、寺院、宿のテキストのポインタテーブルは ROM 内の位置が未特定なので、
+/// 呼び出し側が [`TextTable`] で指定したときのみその分類になり、指定しなければ
+/// [`TextCategory::Unverified`] になる。
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum TextCategory {
+    /// アイテム名 (アイテムデータの名前ポインタから参照される)。
+    ItemName,
+    /// モンスター名の単数形 (モンスターデータの名前ポインタから参照される)。
+    MonsterName,
+    /// モンスター名の複数形 (直前の単数形の終端の直後に置かれる)。
+    MonsterNamePlural,
+    /// イベントメッセージの先頭行 (メッセージテーブルから参照される)。
+    EventMessage,
+    /// イベントメッセージの 2 行目以降 (直前の行の終端の直後に置かれる)。
+    EventMessageLine,
+    /// 会話。
+    Dialogue,
+    /// メニュー。
+    Menu,
+    /// 商店 (ボルタック商店)。
+    Shop,
+    /// 寺院 (カント寺院)。
+    Temple,
+    /// 宿 (冒険者の宿)。
+    Inn,
+    /// 既知のテーブルから辿れず、バイト列とポインタの候補から推測したもの。
+    ///
+    /// コードやデータの誤検出や、ポインタの誤認を含みうる。
+    Unverified,
+}
+
+impl TextCategory {
+    /// 正式名称を返す。
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::ItemName => "item",
+            Self::MonsterName => "monster",
+            Self::MonsterNamePlural => "monster_plural",
+            Self::EventMessage => "event",
+            Self::EventMessageLine => "event_line",
+            Self::Dialogue => "dialogue",
+            Self::Menu => "menu",
+            Self::Shop => "shop",
+            Self::Temple => "temple",
+            Self::Inn => "inn",
+            Self::Unverified => "unverified",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        [
+            Self::ItemName,
+            Self::MonsterName,
+            Self::MonsterNamePlural,
+            Self::EventMessage,
+            Self::EventMessageLine,
+            Self::Dialogue,
+            Self::Menu,
+            Self::Shop,
+            Self::Temple,
+            Self::Inn,
+            Self::Unverified,
+        ]
+        .into_iter()
+        .find(|category| category.name() == name)
+    }
+
+    /// 直前のテキストの続きとして置かれ、自身を指すポインタを持たない分類かどうか。
+    pub fn is_continuation(self) -> bool {
+        matches!(self, Self::MonsterNamePlural | Self::EventMessageLine)
+    }
+}
+
+/// テキストのポインタテーブル。
+///
+/// `table` から 2 バイト (リトルエンディアン) のポインタが `count` 個並び、それぞれが PRG バンク
+/// `text_bank` 内の 0 終端されたテキストを指す。
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct TextTable {
+    pub category: TextCategory,
+    pub table: PrgLocation,
+    pub count: usize,
+    pub text_bank: usize,
+}
+
+impl TextTable {
+    pub fn new(category: TextCategory, table: PrgLocation, count: usize, text_bank: usize) -> Self {
+        assert!(table.offset + 2 * count <= 0x2000);
+        assert!(text_bank < PRG_BANK_COUNT);
+
+        Self {
+            category,
+            table,
+            count,
+            text_bank,
+        }
+    }
+}
+
+impl std::fmt::Display for TextTable {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{},{},{},{}",
+            self.category.name(),
+            self.table,
+            self.count,
+            self.text_bank
+        )
+    }
+}
+
+impl std::str::FromStr for TextTable {
+    type Err = anyhow::Error;
+
+    /// `分類,BB:AAAA,個数,テキストのバンク` の形式から作る。
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let fields: Vec<&str> = s.split(',').collect();
+        ensure!(
+            fields.len() == 4,
+            "text table must be 'category,BB:AAAA,count,text_bank'"
+        );
+
+        let Some(category) = TextCategory::from_name(fields[0]) else {
+            bail!("invalid category: '{}'", fields[0]);
+        };
+        let table: PrgLocation = fields[1].parse()?;
+        let count: usize = fields[2].parse().context("invalid count")?;
+        let text_bank: usize = fields[3].parse().context("invalid text bank")?;
+        ensure!(
+            table.offset + 2 * count <= 0x2000,
+            "text table {s} crosses the end of bank {}",
+            table.bank
+        );
+        ensure!(
+            text_bank < PRG_BANK_COUNT,
+            "text bank must be in 0..{PRG_BANK_COUNT}, got {text_bank}"
+        );
+
+        Ok(Self::new(category, table, count, text_bank))
+    }
+}
+
+/// ROM 内のテキスト 1 個。
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TextEntry {
+    pub location: PrgLocation,
+    /// 元のテキストが占める長さ (終端の 0 を含まない)。
+    pub len: usize,
+    /// このテキストを指すポインタの位置。
+    pub pointers: Vec<PrgLocation>,
+    pub category: TextCategory,
+    pub text: GameString,
+}
+
+const TEXT_TABLE_HEADER: &str = "location\tlen\tcategory\tpointers\ttext";
+
+/// テキストを翻訳用の TSV 表として書き出す。
+///
+/// テキスト中のタブ、改行、バックスラッシュはエスケープされる。
+pub fn write_text_table<W>(mut wtr: W, entries: &[TextEntry]) -> anyhow::Result<()>
+where
+    W: Write,
+{
+    writeln!(wtr, "{TEXT_TABLE_HEADER}")?;
+
+    for entry in entries {
+        let pointers: Vec<String> = entry.pointers.iter().map(|p| p.to_string()).collect();
+        writeln!(
+            wtr,
+            "{}\t{}\t{}\t{}\t{}",
+            entry.location,
+            entry.len,
+            entry.category.name(),
+            pointers.join(","),
+            escape_tsv(&entry.text.to_string()),
+        )?;
+    }
+
+    Ok(())
+}
+
+/// [`write_text_table`] で書き出した表を読み込む。テキスト列は編集済みでもよい。
+pub fn read_text_table<R>(rdr: R) -> anyhow::Result<Vec<TextEntry>>
+where
+    R: BufRead,
+{
+    let mut entries = Vec::<TextEntry>::new();
+
+    for (i, line) in rdr.lines().enumerate() {
+        let line = line?;
+        if i == 0 {
+            ensure!(line == TEXT_TABLE_HEADER, "invalid header: '{line}'");
+            continue;
+        }
+        if line.is_empty() {
+            continue;
+        }
+
+        let entry = parse_text_table_row(&line).with_context(|| format!("line {}", i + 1))?;
+        entries.push(entry);
+    }
+
+    Ok(entries)
+}
+
+fn parse_text_table_row(line: &str) -> anyhow::Result<TextEntry> {
+    let fields: Vec<&str> = line.split('\t').collect();
+    ensure!(fields.len() == 5, "expected 5 fields, got {}", fields.len());
+
+    let location = fields[0].parse()?;
+    let len = fields[1].parse().context("invalid len")?;
+    let Some(category) = TextCategory::from_name(fields[2]) else {
+        bail!("invalid category: '{}'", fields[2]);
+    };
+    let pointers = fields[3]
+        .split(',')
+        .filter(|s| !s.is_empty())
+        .map(str::parse)
+        .collect::<anyhow::Result<Vec<_>>>()?;
+    let text = GameString::from_text(&unescape_tsv(fields[4])?)?;
+
+    Ok(TextEntry {
+        location,
+        len,
+        pointers,
+        category,
+        text,
+    })
+}
+
+fn escape_tsv(s: &str) -> String {
+    s.replace('\\', "\\\\")
+        .replace('\t', "\\t")
+        .replace('\n', "\\n")
+}
+
+fn unescape_tsv(s: &str) -> anyhow::Result<String> {
+    let mut res = String::with_capacity(s.len());
+
+    let mut chars = s.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            res.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('\\') => res.push('\\'),
+            Some('t') => res.push('\t'),
+            Some('n') => res.push('\n'),
+            c => bail!(
+                "invalid escape sequence: \\{}",
+                c.map(String::from).unwrap_or_default()
+            ),
+        }
+    }
+
+    Ok(res)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_text_table_roundtrip() {
+        let entries = vec![TextEntry {
+            location: PrgLocation::new(11, 0x123),
+            len: 9,
+            pointers: vec![PrgLocation::new(11, 0x10), PrgLocation::new(15, 0x1FF0)],
+            category: TextCategory::EventMessage,
+            text: GameString::from_text("A\\B 巻之FIRE").unwrap(),
+        }];
+
+        let mut buf = Vec::<u8>::new();
+        write_text_table(&mut buf, &entries).unwrap();
+
+        assert_eq!(read_text_table(buf.as_slice()).unwrap(), entries);
+    }
+
+    #[test]
+    fn test_text_table_from_str() {
+        let table: TextTable = "shop,15:E000,12,3".parse().unwrap();
+        assert_eq!(
+            table,
+            TextTable::new(TextCategory::Shop, PrgLocation::new(15, 0), 12, 3)
+        );
+        assert_eq!(table.to_string(), "shop,15:E000,12,3");
+
+        assert!("shop,15:FFFE,2,3".parse::<TextTable>().is_err());
+        assert!("shop,15:E000,12,16".parse::<TextTable>().is_err());
+        assert!("foo,15:E000,12,3".parse::<TextTable>().is_err());
+    }
+
+    #[test]
+    fn test_prg_location() {
+        let loc = PrgLocation::new(15, 0x1FF0);
+        assert_eq!(loc.address(), 0xFFF0);
+        assert_eq!(loc.to_string(), "15:FFF0");
+        assert_eq!("15:FFF0".parse::<PrgLocation>().unwrap(), loc);
+        assert!("03:C000".parse::<PrgLocation>().is_err());
+        assert!("16:8000".parse::<PrgLocation>().is_err());
+    }
+}
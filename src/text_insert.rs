@@ -0,0 +1,526 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use anyhow::{bail, ensure, Context as _};
+
+use crate::extract::{extract_texts, ITEM_NAME_LEN_MAX};
+use crate::rom::Rom;
+use crate::string::GameString;
+use crate::text::{PrgLocation, TextCategory, TextEntry, TextTable};
+
+const PRG_BANK_LEN: usize = 0x2000;
+
+/// テキストの移動先として使ってよい領域。
+///
+/// ROM 内の未使用領域は自動では判定しない (`0xFF` の連続などが本当に未使用かは分からないため)。
+/// 呼び出し側が解析済みの空き領域を指定する。
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct FreeRegion {
+    pub location: PrgLocation,
+    pub len: usize,
+}
+
+impl FreeRegion {
+    pub fn new(location: PrgLocation, len: usize) -> Self {
+        assert!(location.offset + len <= PRG_BANK_LEN);
+
+        Self { location, len }
+    }
+}
+
+impl std::fmt::Display for FreeRegion {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}+{}", self.location, self.len)
+    }
+}
+
+impl std::str::FromStr for FreeRegion {
+    type Err = anyhow::Error;
+
+    /// `BB:AAAA+LEN` の形式 (LEN は 10 進のバイト数) から作る。
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (location, len) = s
+            .split_once('+')
+            .context("free region must be 'BB:AAAA+LEN'")?;
+        let location: PrgLocation = location.parse()?;
+        let len: usize = len.parse().context("invalid len")?;
+        ensure!(
+            location.offset + len <= PRG_BANK_LEN,
+            "free region {s} crosses the end of bank {}",
+            location.bank
+        );
+
+        Ok(Self::new(location, len))
+    }
+}
+
+/// テキスト挿入の結果。
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct TextInsertReport {
+    /// 書き換えたテキストの数。
+    pub updated_count: usize,
+    /// 移動したテキスト (移動元, 移動先)。
+    pub relocated: Vec<(PrgLocation, PrgLocation)>,
+    /// バンクごとの空き領域の状況。
+    pub banks: BTreeMap<usize, BankUsage>,
+    /// 挿入できなかったテキスト (位置, 理由)。
+    pub failures: Vec<(PrgLocation, String)>,
+}
+
+/// 1 バンク分の空き領域の状況。
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct BankUsage {
+    /// 挿入前の空き領域のバイト数。
+    pub free_before: usize,
+    /// 挿入後の空き領域のバイト数。
+    pub free_after: usize,
+    /// 空き領域が足りず、収まらなかったバイト数。
+    pub overflow: usize,
+}
+
+/// 編集済みのテキストを ROM に書き戻す。
+///
+/// `entries` は [`crate::extract::extract_texts`] の結果を編集したもの。位置でエントリを照合し、
+/// 元の長さ・分類・ポインタは `entries` ではなく ROM から改めて抽出したものを使う。
+/// `tables` には抽出時と同じテキストのポインタテーブルを指定する。
+/// テキストが変更されていないエントリは無視される。
+///
+/// * アイテム名は元の名前と終端が占めていた範囲に収まれば上書きし、収まらなければ
+///   アイテムデータのバンクの空き領域へ移動して、アイテムデータの名前ポインタを書き換える。
+/// * モンスター名は単数形と複数形の組、イベントメッセージは全行を 1 組として扱い、
+///   収まらなければ組ごと移動してテーブルのポインタを書き換える。
+/// * `tables` で指定したテーブルのテキストは、収まらなければ移動してテーブルのポインタを書き換える。
+/// * 分類が [`TextCategory::Unverified`] のテキストは、ポインタが確かでないので元の範囲内での
+///   上書きのみ行い、移動はしない。
+///
+/// 移動先は `free_regions` で指定した領域のみ。移動元の領域は他から参照されていないことを
+/// 確認できないので再利用しない。
+pub fn insert_texts(
+    rom: &mut Rom,
+    entries: &[TextEntry],
+    tables: &[TextTable],
+    free_regions: &[FreeRegion],
+) -> TextInsertReport {
+    let mut report = TextInsertReport::default();
+    let mut free = FreeSpace::new(free_regions);
+
+    for (&bank, regions) in &free.regions {
+        report.banks.entry(bank).or_default().free_before = regions.iter().map(|r| r.1).sum();
+    }
+
+    let groups = text_groups(extract_texts(rom, tables));
+
+    let edits: BTreeMap<PrgLocation, &GameString> = entries
+        .iter()
+        .map(|entry| (entry.location, &entry.text))
+        .collect();
+
+    // 既知のテキストに該当しない位置は書き込まない。
+    let known: BTreeSet<PrgLocation> = groups.iter().flatten().map(|part| part.location).collect();
+    for &location in edits.keys() {
+        if !known.contains(&location) {
+            report
+                .failures
+                .push((location, "no known text at this location".to_owned()));
+        }
+    }
+
+    for group in &groups {
+        let texts: Vec<&GameString> = group
+            .iter()
+            .map(|part| edits.get(&part.location).copied().unwrap_or(&part.text))
+            .collect();
+        if group
+            .iter()
+            .zip(&texts)
+            .all(|(part, &text)| part.text == *text)
+        {
+            continue;
+        }
+
+        let head = &group[0];
+        let res = match head.category {
+            TextCategory::ItemName => insert_item_name(rom, &mut free, head, texts[0], &mut report),
+            TextCategory::Unverified => insert_in_place(rom, head, texts[0]),
+            _ => insert_group(rom, &mut free, group, &texts, &mut report),
+        };
+
+        match res {
+            Ok(()) => report.updated_count += group.len(),
+            Err(e) => report.failures.push((head.location, e.to_string())),
+        }
+    }
+
+    for (&bank, regions) in &free.regions {
+        report.banks.entry(bank).or_default().free_after = regions.iter().map(|r| r.1).sum();
+    }
+    for (bank, overflow) in free.overflow {
+        report.banks.entry(bank).or_default().overflow = overflow;
+    }
+
+    report
+}
+
+/// 抽出したテキストを、続きの部分 (モンスター名の複数形、イベントメッセージの 2 行目以降) と
+/// 組にする。各組の先頭要素がポインタから参照される。
+fn text_groups(entries: Vec<TextEntry>) -> Vec<Vec<TextEntry>> {
+    let mut groups = Vec::<Vec<TextEntry>>::new();
+
+    for entry in entries {
+        if entry.category.is_continuation() {
+            let prev = groups.last_mut().and_then(|group| group.last());
+            let contiguous = prev.is_some_and(|prev| {
+                entry.location
+                    == PrgLocation::new(prev.location.bank, prev.location.offset + prev.len + 1)
+            });
+            if contiguous {
+                groups.last_mut().unwrap().push(entry);
+            }
+            continue;
+        }
+        groups.push(vec![entry]);
+    }
+
+    groups
+}
+
+fn insert_item_name(
+    rom: &mut Rom,
+    free: &mut FreeSpace,
+    entry: &TextEntry,
+    text: &GameString,
+    report: &mut TextInsertReport,
+) -> anyhow::Result<()> {
+    let mut bytes = text.to_bytes();
+    ensure!(
+        bytes.len() <= ITEM_NAME_LEN_MAX,
+        "item name too long: {} bytes (max {ITEM_NAME_LEN_MAX})",
+        bytes.len()
+    );
+    if bytes.len() < ITEM_NAME_LEN_MAX {
+        bytes.push(0);
+    }
+
+    // 元の名前が占めていた範囲 (16 バイトに満たなければ終端を含む)。
+    let capacity = if entry.len < ITEM_NAME_LEN_MAX {
+        entry.len + 1
+    } else {
+        entry.len
+    };
+
+    if bytes.len() <= capacity {
+        write_padded(rom, entry.location, capacity, &bytes);
+        return Ok(());
+    }
+
+    relocate(rom, free, entry, &bytes, report)
+}
+
+/// 元の範囲 (終端を含む) に収まる場合のみ上書きする。
+fn insert_in_place(rom: &mut Rom, entry: &TextEntry, text: &GameString) -> anyhow::Result<()> {
+    let bytes = terminated_bytes(text);
+    ensure!(
+        bytes.len() <= entry.len + 1,
+        "unverified text cannot be relocated: {} bytes (max {})",
+        bytes.len() - 1,
+        entry.len
+    );
+
+    write_padded(rom, entry.location, entry.len + 1, &bytes);
+
+    Ok(())
+}
+
+/// 0 終端のテキストの組を書き込む。元の範囲に収まらなければ組ごと移動する。
+fn insert_group(
+    rom: &mut Rom,
+    free: &mut FreeSpace,
+    group: &[TextEntry],
+    texts: &[&GameString],
+    report: &mut TextInsertReport,
+) -> anyhow::Result<()> {
+    let head = &group[0];
+
+    let mut old_len: usize = group.iter().map(|part| part.len + 1).sum();
+    let mut bytes: Vec<u8> = texts
+        .iter()
+        .flat_map(|text| terminated_bytes(text))
+        .collect();
+    // イベントメッセージは空行で終わる。
+    if head.category == TextCategory::EventMessage {
+        old_len += 1;
+        bytes.push(0);
+    }
+
+    if bytes.len() <= old_len {
+        write_padded(rom, head.location, old_len, &bytes);
+        return Ok(());
+    }
+
+    relocate(rom, free, head, &bytes, report)
+}
+
+/// `bytes` を空き領域へ書き込み、`head` を指すポインタを書き換える。
+fn relocate(
+    rom: &mut Rom,
+    free: &mut FreeSpace,
+    head: &TextEntry,
+    bytes: &[u8],
+    report: &mut TextInsertReport,
+) -> anyhow::Result<()> {
+    let bank_id = head.location.bank;
+
+    ensure!(
+        !head.pointers.is_empty(),
+        "text grew but no known pointer refers to it"
+    );
+
+    let Some(offset) = free.alloc(bank_id, bytes.len()) else {
+        *free.overflow.entry(bank_id).or_default() += bytes.len();
+        bail!("no free space in bank {bank_id} for {} bytes", bytes.len());
+    };
+
+    let dest = PrgLocation::new(bank_id, offset);
+    rom.prg_bank_mut(bank_id)[offset..][..bytes.len()].copy_from_slice(bytes);
+
+    let address = dest.address().to_le_bytes();
+    for ptr in &head.pointers {
+        rom.prg_bank_mut(ptr.bank)[ptr.offset..][..2].copy_from_slice(&address);
+    }
+
+    report.relocated.push((head.location, dest));
+
+    Ok(())
+}
+
+/// `location` から `len` バイトの範囲に `bytes` を書き込み、残りを 0 で埋める。
+fn write_padded(rom: &mut Rom, location: PrgLocation, len: usize, bytes: &[u8]) {
+    let area = &mut rom.prg_bank_mut(location.bank)[location.offset..][..len];
+    area.fill(0);
+    area[..bytes.len()].copy_from_slice(bytes);
+}
+
+fn terminated_bytes(s: &GameString) -> Vec<u8> {
+    let mut bytes = s.to_bytes();
+    bytes.push(0);
+
+    bytes
+}
+
+/// バンクごとの空き領域 (オフセット, 長さ)。
+#[derive(Debug)]
+struct FreeSpace {
+    regions: BTreeMap<usize, Vec<(usize, usize)>>,
+    overflow: BTreeMap<usize, usize>,
+}
+
+impl FreeSpace {
+    fn new(free_regions: &[FreeRegion]) -> Self {
+        let mut regions = BTreeMap::<usize, Vec<(usize, usize)>>::new();
+
+        for region in free_regions {
+            if region.len > 0 {
+                regions
+                    .entry(region.location.bank)
+                    .or_default()
+                    .push((region.location.offset, region.len));
+            }
+        }
+
+        Self {
+            regions,
+            overflow: BTreeMap::new(),
+        }
+    }
+
+    /// 指定したバンクから `len` バイトを確保し、そのオフセットを返す (first fit)。
+    fn alloc(&mut self, bank: usize, len: usize) -> Option<usize> {
+        let regions = self.regions.get_mut(&bank)?;
+
+        let i = regions.iter().position(|&(_, rlen)| rlen >= len)?;
+        let (offset, rlen) = regions[i];
+        if rlen == len {
+            regions.remove(i);
+        } else {
+            regions[i] = (offset + len, rlen - len);
+        }
+
+        Some(offset)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::test_util::blank_ines;
+
+    /// アイテム 0 の名前、モンスター 0 の名前、イベントメッセージ 0 のみを持つ ROM を作る。
+    fn make_rom() -> Rom {
+        let mut ines = blank_ines();
+        let prg = &mut ines[16..];
+
+        let bank = &mut prg[0x2000 * 8..][..0x2000];
+        bank[..2].copy_from_slice(&0x9100_u16.to_le_bytes());
+        bank[2..4].copy_from_slice(&0x9107_u16.to_le_bytes());
+        bank[0x1100..0x110F].copy_from_slice(b"DAGGER\0WEAPON\0\0");
+
+        let bank = &mut prg[0x2000 * 6..][..0x2000];
+        bank[..2].copy_from_slice(&0x9000_u16.to_le_bytes());
+        bank[0x1000..0x1002].copy_from_slice(&0x9100_u16.to_le_bytes());
+        bank[0x1002..0x1004].copy_from_slice(&0x9100_u16.to_le_bytes());
+        bank[0x1100..0x110D].copy_from_slice(b"SLIME\0SLIMES\0");
+
+        let bank = &mut prg[0x2000 * 11..][..0x2000];
+        bank[..2].copy_from_slice(&0x8100_u16.to_le_bytes());
+        bank[0x100..0x108].copy_from_slice(b"ABC\0DE\0\0");
+        // 偶然メッセージのアドレスと一致する値 (ポインタではない)
+        bank[0x1F00..0x1F02].copy_from_slice(&0x8100_u16.to_le_bytes());
+
+        Rom::from_ines_bytes(&ines).unwrap()
+    }
+
+    fn edit(bank: usize, offset: usize, text: &str) -> TextEntry {
+        TextEntry {
+            location: PrgLocation::new(bank, offset),
+            len: 0,
+            pointers: vec![],
+            category: TextCategory::Unverified,
+            text: GameString::from_text(text).unwrap(),
+        }
+    }
+
+    fn free_regions() -> Vec<FreeRegion> {
+        vec![
+            FreeRegion::new(PrgLocation::new(8, 0x1800), 0x100),
+            FreeRegion::new(PrgLocation::new(11, 0x1000), 0x100),
+        ]
+    }
+
+    #[test]
+    fn test_insert_event_in_place() {
+        let mut rom = make_rom();
+
+        let report = insert_texts(&mut rom, &[edit(11, 0x100, "XY")], &[], &free_regions());
+
+        assert_eq!(report.updated_count, 2);
+        assert!(report.relocated.is_empty());
+        assert_eq!(&rom.prg_bank(11)[0x100..0x108], b"XY\0DE\0\0\0");
+    }
+
+    #[test]
+    fn test_insert_event_relocate() {
+        let mut rom = make_rom();
+
+        let report = insert_texts(&mut rom, &[edit(11, 0x100, "LONGER")], &[], &free_regions());
+
+        assert_eq!(
+            report.relocated,
+            [(PrgLocation::new(11, 0x100), PrgLocation::new(11, 0x1000))]
+        );
+        let bank = rom.prg_bank(11);
+        assert_eq!(&bank[..2], &0x9000_u16.to_le_bytes());
+        assert_eq!(&bank[0x1000..0x100B], b"LONGER\0DE\0\0");
+        // ポインタテーブル以外の値は書き換えない
+        assert_eq!(&bank[0x1F00..0x1F02], &0x8100_u16.to_le_bytes());
+        assert_eq!(report.banks[&11].free_before, 0x100);
+        assert_eq!(report.banks[&11].free_after, 0x100 - 11);
+    }
+
+    #[test]
+    fn test_insert_item_name() {
+        let mut rom = make_rom();
+
+        // 短くなる場合は元の範囲内のみ書き換え、隣の名前は壊さない
+        let report = insert_texts(&mut rom, &[edit(8, 0x1100, "DIRK")], &[], &free_regions());
+        assert!(report.failures.is_empty());
+        assert_eq!(&rom.prg_bank(8)[0x1100..0x110E], b"DIRK\0\0\0WEAPON\0");
+
+        // 長くなる場合は移動してアイテムデータの名前ポインタを書き換える
+        let report = insert_texts(
+            &mut rom,
+            &[edit(8, 0x1107, "LONG WEAPON")],
+            &[],
+            &free_regions(),
+        );
+        assert_eq!(
+            report.relocated,
+            [(PrgLocation::new(8, 0x1107), PrgLocation::new(8, 0x1800))]
+        );
+        let bank = rom.prg_bank(8);
+        assert_eq!(&bank[..4], &[0x00, 0x91, 0x00, 0x98]);
+        assert_eq!(&bank[0x1800..0x180C], b"LONG WEAPON\0");
+
+        let report = insert_texts(&mut rom, &[edit(8, 0x1100, "ABCDEFGHIJKLMNOPQ")], &[], &[]);
+        assert_eq!(report.failures.len(), 1);
+    }
+
+    #[test]
+    fn test_insert_table_text_relocate() {
+        let mut ines = blank_ines();
+        let prg = &mut ines[16..];
+        prg[0x2000 * 15 + 0x100..][..2].copy_from_slice(&0x8010_u16.to_le_bytes());
+        prg[0x2000 * 3 + 0x10..][..4].copy_from_slice(b"INN\0");
+        let mut rom = Rom::from_ines_bytes(&ines).unwrap();
+        let tables = [TextTable::new(
+            TextCategory::Inn,
+            PrgLocation::new(15, 0x100),
+            1,
+            3,
+        )];
+        let free_regions = [FreeRegion::new(PrgLocation::new(3, 0x1000), 0x10)];
+
+        let report = insert_texts(&mut rom, &[edit(3, 0x10, "HOTEL")], &tables, &free_regions);
+
+        assert!(report.failures.is_empty());
+        assert_eq!(
+            report.relocated,
+            [(PrgLocation::new(3, 0x10), PrgLocation::new(3, 0x1000))]
+        );
+        assert_eq!(&rom.prg_bank(15)[0x100..0x102], &0x9000_u16.to_le_bytes());
+        assert_eq!(&rom.prg_bank(3)[0x1000..0x1006], b"HOTEL\0");
+
+        // テーブルを指定しなければ既知のテキストではない
+        let report = insert_texts(&mut rom, &[edit(3, 0x1000, "X")], &[], &free_regions);
+        assert_eq!(report.failures.len(), 1);
+    }
+
+    #[test]
+    fn test_insert_monster_name_no_free_space() {
+        let mut rom = make_rom();
+        let orig = rom.prg().to_vec();
+
+        let report = insert_texts(
+            &mut rom,
+            &[edit(6, 0x1106, "SLIMES!")],
+            &[],
+            &free_regions(),
+        );
+
+        assert_eq!(report.updated_count, 0);
+        assert_eq!(report.failures.len(), 1);
+        assert_eq!(report.failures[0].0, PrgLocation::new(6, 0x1100));
+        assert_eq!(report.banks.get(&6).map(|usage| usage.overflow), Some(14));
+        assert_eq!(rom.prg(), orig.as_slice());
+    }
+
+    #[test]
+    fn test_insert_unknown_location() {
+        let mut rom = make_rom();
+        let orig = rom.prg().to_vec();
+
+        let report = insert_texts(&mut rom, &[edit(11, 0x101, "X")], &[], &free_regions());
+
+        assert_eq!(report.failures.len(), 1);
+        assert_eq!(rom.prg(), orig.as_slice());
+    }
+
+    #[test]
+    fn test_free_region_from_str() {
+        assert_eq!(
+            "11:9000+256".parse::<FreeRegion>().unwrap(),
+            FreeRegion::new(PrgLocation::new(11, 0x1000), 256)
+        );
+        assert!("11:9F00+512".parse::<FreeRegion>().is_err());
+        assert!("16:9000+256".parse::<FreeRegion>().is_err());
+    }
+}
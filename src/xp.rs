@@ -0,0 +1,142 @@
+use anyhow::{ensure, Context as _};
+
+/// 職業ごとの経験値テーブル。
+///
+/// テーブルの範囲を超えるレベルでは、1 レベルごとに必要経験値が一定値ずつ増える。
+///
+/// ROM 内のテーブルの位置は未特定なので、値は利用者が与える。
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct XpTable {
+    /// レベル 2, 3, ... に達するのに必要な累計経験値。
+    pub thresholds: Vec<u64>,
+    /// テーブルの範囲を超えた後、1 レベル上がるごとに必要な経験値。
+    pub post_increment: u64,
+}
+
+impl XpTable {
+    /// 指定したレベルに達するのに必要な累計経験値を返す。
+    ///
+    /// `level` は 1 以上でなければならない。
+    pub fn xp_for_level(&self, level: u32) -> u64 {
+        assert!(level >= 1);
+
+        let idx = (level - 1) as usize;
+        if idx == 0 {
+            return 0;
+        }
+
+        match self.thresholds.get(idx - 1) {
+            Some(&xp) => xp,
+            None => {
+                let last = self.thresholds.last().copied().unwrap_or(0);
+                let excess = (idx - self.thresholds.len()) as u64;
+                last.saturating_add(excess.saturating_mul(self.post_increment))
+            }
+        }
+    }
+
+    /// 指定したレベルから次のレベルに上がるのに必要な経験値を返す。
+    ///
+    /// `level` は 1 以上でなければならない。次のレベルが `u32` で表せなければ `None` を返す。
+    pub fn xp_to_next_level(&self, level: u32) -> Option<u64> {
+        let next = level.checked_add(1)?;
+
+        Some(
+            self.xp_for_level(next)
+                .saturating_sub(self.xp_for_level(level)),
+        )
+    }
+
+    /// 累計経験値から到達しているレベルを返す。
+    pub fn level_for_xp(&self, xp: u64) -> u32 {
+        let in_table = self
+            .thresholds
+            .partition_point(|&threshold| threshold <= xp);
+        if in_table < self.thresholds.len() || self.post_increment == 0 {
+            return 1 + in_table as u32;
+        }
+
+        let last = self.thresholds.last().copied().unwrap_or(0);
+        let excess = (xp - last) / self.post_increment;
+
+        (1 + in_table as u32).saturating_add(u32::try_from(excess).unwrap_or(u32::MAX))
+    }
+}
+
+impl std::str::FromStr for XpTable {
+    type Err = anyhow::Error;
+
+    /// `閾値,閾値,...+増分` の形式 (例: `1000,1724,2903+289000`) から作る。
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (thresholds, post_increment) = s
+            .split_once('+')
+            .context("XP table must be 'xp,xp,...+increment'")?;
+
+        let thresholds = thresholds
+            .split(',')
+            .map(|xp| {
+                xp.trim()
+                    .parse::<u64>()
+                    .with_context(|| format!("invalid XP: '{xp}'"))
+            })
+            .collect::<anyhow::Result<Vec<_>>>()?;
+        ensure!(
+            thresholds.is_sorted(),
+            "XP thresholds must be in ascending order"
+        );
+        let post_increment: u64 = post_increment
+            .trim()
+            .parse()
+            .context("invalid XP increment")?;
+
+        Ok(Self {
+            thresholds,
+            post_increment,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_xp_table() {
+        let table = XpTable {
+            thresholds: vec![1000, 1724, 2903],
+            post_increment: 289_000,
+        };
+
+        assert_eq!(table.xp_for_level(1), 0);
+        assert_eq!(table.xp_for_level(2), 1000);
+        assert_eq!(table.xp_for_level(4), 2903);
+        assert_eq!(table.xp_for_level(5), 291_903);
+        assert_eq!(table.xp_for_level(6), 580_903);
+
+        assert_eq!(table.level_for_xp(0), 1);
+        assert_eq!(table.level_for_xp(999), 1);
+        assert_eq!(table.level_for_xp(1000), 2);
+        assert_eq!(table.level_for_xp(291_902), 4);
+        assert_eq!(table.level_for_xp(291_903), 5);
+        assert_eq!(table.level_for_xp(580_903), 6);
+
+        assert_eq!(table.xp_to_next_level(1), Some(1000));
+        assert_eq!(table.xp_to_next_level(4), Some(289_000));
+        assert_eq!(table.xp_to_next_level(u32::MAX), None);
+
+        for level in 1..=10 {
+            assert_eq!(table.level_for_xp(table.xp_for_level(level)), level);
+        }
+    }
+
+    #[test]
+    fn test_xp_table_from_str() {
+        let table: XpTable = "1000,1724,2903+289000".parse().unwrap();
+        assert_eq!(table.thresholds, [1000, 1724, 2903]);
+        assert_eq!(table.post_increment, 289_000);
+
+        assert!("1000,1724,2903".parse::<XpTable>().is_err());
+        assert!("1724,1000+289000".parse::<XpTable>().is_err());
+        assert!("1000,x+289000".parse::<XpTable>().is_err());
+    }
+}